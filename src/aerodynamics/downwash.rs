@@ -0,0 +1,51 @@
+use std::f64::consts::PI;
+
+pub enum DownwashModel {
+    Simple,   // elliptic wing far upstream of the tail
+    Datcom,   // empirical, with tail height, sweep and mach
+    ChartFit, // curve fit of the Roskam / DATCOM charts
+}
+
+impl DownwashModel {
+    /// 0 for simple, 1 for DATCOM, 2 for the chart fit
+    pub fn from_code(code: f64) -> Result<Self, String> {
+        let model = match code as i32 {
+            0 => DownwashModel::Simple,
+            1 => DownwashModel::Datcom,
+            2 => DownwashModel::ChartFit,
+            _ => return Err(format!("unknown downwash model {}, expected 0, 1 or 2", code)),
+        };
+        Ok(model)
+    }
+}
+
+/// Variation of downwash angle with wing angle of attack for an elliptic
+/// lift distribution, tail far behind the wing.
+/// - c_law: wing lift curve slope (per rad)
+/// - ar: wing aspect ratio
+pub fn simple(c_law: f64, ar: f64) -> f64 {
+    2. * c_law / (PI * ar)
+}
+
+/// Variation of downwash angle with wing angle of attack (empirical).
+/// ar: wing aspect ratio
+/// lambda: wing taper ratio
+/// h_h: wing chord to horizontal tail chord vertical distance
+/// l_h: wing quarter chord position to horizontal tail quarter chord position horizontal distance
+/// b: wing span
+/// sweep: wing quarter chord sweep (radians)
+/// k_m: mach correction, wing lift curve slope at mach cruise over lift curve slope at mach 0
+pub fn datcom(ar: f64, lambda: f64, h_h: f64, l_h: f64, b: f64, sweep: f64, k_m: f64) -> f64 {
+    let k_a = 1. / ar - 1. / (1. + ar.powf(1.7));
+    let k_lambda = (10. - 3.*lambda) / 7.;
+    let k_h = (1. - f64::abs(h_h/b)) / f64::powf(2. * l_h / b, 1./3.);
+    4.44 * f64::powf(k_a * k_lambda * k_h * sweep.cos().sqrt(), 1.19) * k_m
+}
+
+/// Variation of downwash angle with wing angle of attack fitted to the
+/// Roskam charts, using the same geometry as `datcom`.
+pub fn chart_fit(ar: f64, lambda: f64, h_h: f64, l_h: f64, b: f64, c_law: f64) -> f64 {
+    let r = 2. * l_h / b;
+    let m = 2. * h_h / b;
+    1.75 * c_law / (PI * ar * f64::powf(lambda * r, 0.25) * (1. + m.abs()))
+}
@@ -0,0 +1,90 @@
+use std::f64::consts::PI;
+use crate::Params;
+use crate::aerodynamics::planform::Planform;
+
+/// Fuselage width at a longitudinal station.
+#[derive(Clone)]
+pub struct FuselageStation {
+    x: f64, // position from the nose (ft)
+    w_f: f64, // fuselage width (ft)
+}
+
+impl FuselageStation {
+    pub fn new(x: f64, w_f: f64) -> Self {
+        Self { x, w_f }
+    }
+}
+
+/// Pitching moment slope of the fuselage (and nacelles lumped with it).
+pub enum FuselageMoment {
+    /// Gilruth and White chart method.
+    /// - k_f: empirical factor from graph (per degree)
+    /// - l_f: fuselage length
+    /// - w_f: fuselage max width
+    Gilruth { k_f: f64, l_f: f64, w_f: f64 },
+    /// Multhopp strip integration over the fuselage stations, the strips
+    /// ahead of the wing see the wing upwash and the ones behind it the
+    /// downwash. The part of the fuselage inside the wing root chord is
+    /// carried by the wing.
+    Multhopp { stations: Vec<FuselageStation>, x_le_root: f64, c_root: f64 },
+}
+
+impl FuselageMoment {
+    pub fn gilruth(params: &Params) -> Self {
+        FuselageMoment::Gilruth {
+            k_f: *params.get("k_f").expect("missing k_f"),
+            l_f: *params.get("l_f").expect("missing l_f"),
+            w_f: *params.get("w_f").expect("missing w_f"),
+        }
+    }
+
+    pub fn multhopp(stations: Vec<FuselageStation>, wing: &Planform) -> Self {
+        FuselageMoment::Multhopp {
+            stations,
+            x_le_root: *wing.root().x(),
+            c_root: wing.root_chord(),
+        }
+    }
+
+    /// Returns the pitching moment slope generated by the fuselage (per rad).
+    /// - c_law: wing lift curve slope (per rad)
+    /// - depsi_da: variation of downwash angle with wing angle of attack
+    /// - x_ach: horizontal tailplane aerodynamic centre
+    /// - c_bar: mean aerodynamic chord
+    /// - s_w: wing reference area
+    pub fn cm_alpha(&self, c_law: f64, depsi_da: f64, x_ach: f64, c_bar: f64, s_w: f64) -> f64 {
+        match self {
+            FuselageMoment::Gilruth { k_f, l_f, w_f } => {
+                k_f * w_f * w_f * l_f / (c_bar * s_w) * 180. / PI
+            }
+            FuselageMoment::Multhopp { stations, x_le_root, c_root } => {
+                let x_te_root = x_le_root + c_root;
+                let sum: f64 = stations.windows(2).map(|strip| {
+                    let dx = strip[1].x - strip[0].x;
+                    let x = 0.5 * (strip[0].x + strip[1].x);
+                    let w_f = 0.5 * (strip[0].w_f + strip[1].w_f);
+                    let dbeta_da = if x < *x_le_root {
+                        1. + upwash(x_le_root - x, *c_root) * c_law / C_LAW_CHART
+                    } else if x > x_te_root {
+                        (x - x_te_root) / (x_ach - x_te_root) * (1. - depsi_da)
+                    } else {
+                        0.
+                    };
+                    w_f * w_f * dbeta_da * dx
+                }).sum();
+                PI / (2. * s_w * c_bar) * sum
+            }
+        }
+    }
+}
+
+/// Lift curve slope the upwash chart is drawn for (0.08 per degree).
+const C_LAW_CHART: f64 = 0.08 * 180. / PI;
+
+/// Variation of upwash angle with wing angle of attack ahead of the wing,
+/// curve fit of the Multhopp chart, capped next to the wing.
+/// - d: distance ahead of the wing root leading edge
+/// - c_root: wing root chord
+fn upwash(d: f64, c_root: f64) -> f64 {
+    f64::min(0.4 * f64::powf(d / c_root, -1.4), 3.)
+}
@@ -0,0 +1,28 @@
+pub mod downwash;
+pub mod fuselage_moment;
+pub mod planform;
+
+use std::f64::consts::PI;
+
+/// Lift curve slope per radian of a straight tapered surface (DATCOM form of
+/// the Helmbold equation), valid for subsonic mach numbers.
+/// - ar: aspect ratio
+/// - sweep_half: half chord sweep in radians
+/// - mach: free stream mach number
+/// - eta: airfoil efficiency, section lift curve slope / 2π (about 0.95)
+pub fn lift_curve_slope(ar: f64, sweep_half: f64, mach: f64, eta: f64) -> f64 {
+    let beta2 = 1. - mach * mach;
+    2. * PI * ar / (
+        2. + f64::sqrt(
+            4. + ar * ar * beta2 / (eta * eta) * (1. + sweep_half.tan().powi(2) / beta2)
+        )
+    )
+}
+
+/// Sweep at the fraction `n` of the chord from the quarter chord sweep.
+/// - sweep_qc: quarter chord sweep in radians
+/// - ar: aspect ratio
+/// - lambda: taper ratio
+pub fn sweep_at(n: f64, sweep_qc: f64, ar: f64, lambda: f64) -> f64 {
+    f64::atan(sweep_qc.tan() - 4. / ar * (n - 0.25) * (1. - lambda) / (1. + lambda))
+}
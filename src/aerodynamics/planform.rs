@@ -0,0 +1,102 @@
+use crate::Params;
+use crate::utils::point::Point;
+use crate::aerodynamics::{lift_curve_slope, sweep_at};
+
+/// Straight tapered lifting surface, both sides together.
+pub struct Planform {
+    s: f64, // reference area (ft2)
+    ar: f64, // aspect ratio
+    lambda: f64, // taper ratio
+    sweep: f64, // quarter chord sweep in radians
+    root: Point<f64>, // leading edge of the root chord
+}
+
+impl Planform {
+    pub fn new(s: f64, ar: f64, lambda: f64, sweep: f64, root: Point<f64>) -> Self {
+        Self { s, ar, lambda, sweep, root }
+    }
+
+    pub fn wing(params: &Params) -> Self {
+        Self::new(
+            *params.get("s_w").expect("missing s_w"),
+            *params.get("ar").expect("missing ar"),
+            *params.get("lambda").expect("missing lambda"),
+            *params.get("sweep").expect("missing sweep"),
+            Point::new(
+                *params.get("x_root_w").expect("missing x_root_w"),
+                *params.get("y_root_w").expect("missing y_root_w"),
+                *params.get("z_root_w").expect("missing z_root_w"),
+            ),
+        )
+    }
+
+    /// Aspect ratio and taper ratio follow from the area, span and root chord.
+    pub fn horizontal_tailplane(params: &Params) -> Self {
+        let s_ht = *params.get("s_ht").expect("missing s_ht");
+        let b_ht = *params.get("b_ht").expect("missing b_ht");
+        let chord_ht = *params.get("chord_ht").expect("missing chord_ht");
+        Self::new(
+            s_ht,
+            b_ht * b_ht / s_ht,
+            2. * s_ht / (b_ht * chord_ht) - 1.,
+            *params.get("sweep_ht").expect("missing sweep_ht"),
+            Point::new(
+                *params.get("x_root_ht").expect("missing x_root_ht"),
+                *params.get("y_root_ht").expect("missing y_root_ht"),
+                *params.get("z_root_ht").expect("missing z_root_ht"),
+            ),
+        )
+    }
+
+    pub fn area(&self) -> f64 {
+        self.s
+    }
+
+    pub fn span(&self) -> f64 {
+        f64::sqrt(self.s * self.ar)
+    }
+
+    pub fn root_chord(&self) -> f64 {
+        2. * self.s / (self.span() * (1. + self.lambda))
+    }
+
+    pub fn tip_chord(&self) -> f64 {
+        self.lambda * self.root_chord()
+    }
+
+    /// Mean aerodynamic chord length (ft).
+    pub fn mac(&self) -> f64 {
+        2. / 3. * self.root_chord() * (1. + self.lambda + self.lambda * self.lambda) / (1. + self.lambda)
+    }
+
+    /// Spanwise station of the mean aerodynamic chord from the centerline (ft).
+    pub fn y_mac(&self) -> f64 {
+        self.span() / 6. * (1. + 2. * self.lambda) / (1. + self.lambda)
+    }
+
+    /// Leading edge of the mean aerodynamic chord (ft).
+    pub fn x_le_mac(&self) -> f64 {
+        self.root.x() + self.y_mac() * self.sweep_le().tan()
+    }
+
+    /// Aerodynamic centre, taken at the quarter of the mean aerodynamic chord (ft).
+    pub fn x_ac(&self) -> f64 {
+        self.x_le_mac() + 0.25 * self.mac()
+    }
+
+    /// Leading edge sweep in radians.
+    pub fn sweep_le(&self) -> f64 {
+        sweep_at(0., self.sweep, self.ar, self.lambda)
+    }
+
+    /// Lift curve slope per radian.
+    /// - mach: free stream mach number
+    /// - eta: airfoil efficiency, section lift curve slope / 2π
+    pub fn lift_curve_slope(&self, mach: f64, eta: f64) -> f64 {
+        lift_curve_slope(self.ar, sweep_at(0.5, self.sweep, self.ar, self.lambda), mach, eta)
+    }
+
+    pub fn root(&self) -> Point<f64> {
+        self.root
+    }
+}
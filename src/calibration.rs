@@ -0,0 +1,167 @@
+use std::error::Error;
+use csv::{Reader, Writer};
+use crate::Params;
+use crate::components::Aircraft;
+
+/// One aircraft of the reference database: the parameters it is known by,
+/// taking the others from the parameters files, and its group weights.
+/// The table has an aircraft column, parameter columns, w_dg among them,
+/// and w_ref_<group> columns, group names in underscores. Empty cells are
+/// unknown.
+pub struct ReferenceAircraft {
+    name: String,
+    params: Params,
+    group_weights: Vec<(String, f64)>, // lb
+}
+
+pub fn read_reference_aircraft(path: &str) -> Result<Vec<ReferenceAircraft>, Box<dyn Error>> {
+    let mut reader = Reader::from_path(path)?;
+    let headers = reader.headers()?.clone();
+    let mut aircraft = Vec::new();
+    for result in reader.records() {
+        let record = result?;
+        let mut reference = ReferenceAircraft {
+            name: String::new(),
+            params: Params::new(),
+            group_weights: Vec::new(),
+        };
+        for (header, value) in headers.iter().zip(record.iter()) {
+            if header == "aircraft" {
+                reference.name = value.to_string();
+            } else if value.is_empty() {
+                continue;
+            } else if let Some(group) = header.strip_prefix("w_ref_") {
+                reference.group_weights.push((group.replace('_', " "), value.parse::<f64>()?));
+            } else {
+                reference.params.insert(header.to_string(), value.parse::<f64>()?);
+            }
+        }
+        aircraft.push(reference);
+    }
+    Ok(aircraft)
+}
+
+/// Estimated and reference weight of a group of one aircraft.
+pub struct Residual {
+    aircraft: String,
+    group: String,
+    reference: f64, // lb
+    estimate: f64, // lb
+    k: f64, // correction factor of the group
+}
+
+impl Residual {
+    pub fn aircraft(&self) -> &str {
+        &self.aircraft
+    }
+
+    pub fn group(&self) -> &str {
+        &self.group
+    }
+
+    pub fn reference(&self) -> f64 {
+        self.reference
+    }
+
+    pub fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    pub fn calibrated(&self) -> f64 {
+        self.k * self.estimate
+    }
+
+    /// Relative error of the estimate
+    pub fn error(&self) -> f64 {
+        self.estimate / self.reference - 1.
+    }
+
+    /// Relative error of the corrected estimate
+    pub fn calibrated_error(&self) -> f64 {
+        self.calibrated() / self.reference - 1.
+    }
+}
+
+/// Correction factor of a group and the items it applies to.
+pub struct GroupFactor {
+    group: String,
+    k: f64,
+    items: Vec<&'static str>,
+}
+
+impl GroupFactor {
+    pub fn group(&self) -> &str {
+        &self.group
+    }
+
+    pub fn k(&self) -> f64 {
+        self.k
+    }
+
+    /// Root mean square of the relative errors, before and after correction.
+    pub fn rms(&self, residuals: &[Residual]) -> (f64, f64) {
+        let errors: Vec<_> = residuals.iter().filter(|r| r.group == self.group).collect();
+        let rms = |e: &dyn Fn(&Residual) -> f64| {
+            f64::sqrt(errors.iter().map(|r| e(r).powi(2)).sum::<f64>() / errors.len() as f64)
+        };
+        (rms(&|r| r.error()), rms(&|r| r.calibrated_error()))
+    }
+}
+
+/// Fits one factor per group, minimising the squares of the logarithmic
+/// errors since the estimators are power laws: k = exp(mean(ln(W_ref/W_est))).
+pub fn calibrate(
+    base: &Params,
+    references: &[ReferenceAircraft],
+) -> Result<(Vec<GroupFactor>, Vec<Residual>), Box<dyn Error>> {
+    let mut residuals = Vec::new();
+    let mut factors: Vec<GroupFactor> = Vec::new();
+    for reference in references {
+        let mut params = base.clone();
+        params.extend(reference.params.iter().map(|(symbol, value)| (symbol.clone(), *value)));
+        let w_dg = *params.get("w_dg").ok_or_else(|| format!("missing w_dg for {}", reference.name))?;
+        let groups = Aircraft::new(&params).estimated_groups(w_dg);
+        for (group, w_ref) in &reference.group_weights {
+            let (_, items) = groups.iter()
+                .find(|(name, _)| name == group)
+                .ok_or_else(|| format!("unknown group {} for {}", group, reference.name))?;
+            residuals.push(Residual {
+                aircraft: reference.name.clone(),
+                group: group.clone(),
+                reference: *w_ref,
+                estimate: items.iter().map(|item| item.weight().get_val()).sum(),
+                k: 1.,
+            });
+            if !factors.iter().any(|f| &f.group == group) {
+                let mut names: Vec<_> = items.iter().map(|item| item.base_name()).collect();
+                names.dedup();
+                factors.push(GroupFactor { group: group.clone(), k: 1., items: names });
+            }
+        }
+    }
+    for factor in &mut factors {
+        let logs: Vec<f64> = residuals.iter()
+            .filter(|r| r.group == factor.group)
+            .map(|r| f64::ln(r.reference / r.estimate))
+            .collect();
+        factor.k = f64::exp(logs.iter().sum::<f64>() / logs.len() as f64);
+        for residual in residuals.iter_mut().filter(|r| r.group == factor.group) {
+            residual.k = factor.k;
+        }
+    }
+    Ok((factors, residuals))
+}
+
+/// Writes the factors as k_calibration_<item> parameters, in the format of
+/// the parameters files.
+pub fn write_factors(path: &str, factors: &[GroupFactor]) -> Result<(), Box<dyn Error>> {
+    let mut writer = Writer::from_path(path)?;
+    writer.write_record(["symbol", "value"])?;
+    for factor in factors {
+        for item in &factor.items {
+            writer.write_record([format!("k_calibration_{}", item.replace(' ', "_")), factor.k.to_string()])?;
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,226 @@
+use std::error::Error;
+use csv::Writer;
+use crate::Params;
+
+const SIZING_ITERATIONS: usize = 50;
+const KNOTS_TO_FT_S: f64 = 1.68781;
+const GROUPS: [&str; 7] = ["wings", "tailplane", "fuselage", "nacelles", "engines", "landing gear", "systems"];
+
+/// Aircraft types of the empty weight regressions.
+#[derive(Copy, Clone, PartialEq)]
+pub enum AircraftType {
+    JetTransport,
+    Turboprop,
+    Regional, // regional turboprop
+}
+
+impl AircraftType {
+    /// 0 for jet transport, 1 for turboprop, 2 for regional turboprop
+    pub fn from_code(code: f64) -> Result<Self, String> {
+        let aircraft_type = match code as i32 {
+            0 => AircraftType::JetTransport,
+            1 => AircraftType::Turboprop,
+            2 => AircraftType::Regional,
+            _ => return Err(format!("unknown aircraft type {}, expected 0, 1 or 2", code)),
+        };
+        Ok(aircraft_type)
+    }
+
+    /// Empty weight fraction W_e/W_0.
+    /// Raymer table 3.1 for jet transports and twin turboprops, Roskam table
+    /// 2.15 (log W_TO = A + B log W_E) for regional turboprops.
+    pub fn empty_weight_fraction(&self, w_0: f64) -> f64 {
+        match self {
+            AircraftType::JetTransport => 1.02 * w_0.powf(-0.06),
+            AircraftType::Turboprop => 0.96 * w_0.powf(-0.05),
+            AircraftType::Regional => {
+                let (a, b) = (0.3774, 0.9647);
+                10_f64.powf((w_0.log10() - a) / b) / w_0
+            }
+        }
+    }
+
+    /// Fuselage length from Raymer table 6.3 (ft).
+    pub fn fuselage_length(&self, w_0: f64) -> f64 {
+        match self {
+            AircraftType::JetTransport => 0.67 * w_0.powf(0.43),
+            AircraftType::Turboprop | AircraftType::Regional => 0.37 * w_0.powf(0.51),
+        }
+    }
+
+    /// Typical cruise lift to drag ratio, specific fuel consumption (1/hr)
+    /// and speed (knots). The turboprop consumptions are the equivalent
+    /// thrust specific ones of c_bhp = 0.5 lb/hr/bhp with a 0.8 propeller
+    /// efficiency.
+    fn cruise(&self) -> (f64, f64, f64) {
+        let turboprop = |v: f64| 0.5 * v * KNOTS_TO_FT_S / (550. * 0.8);
+        match self {
+            AircraftType::JetTransport => (16., 0.5, 470.),
+            AircraftType::Turboprop => (14., turboprop(300.), 300.),
+            AircraftType::Regional => (13., turboprop(270.), 270.),
+        }
+    }
+}
+
+//---------------------------------------------------- class I estimate
+
+/// Conceptual stage estimate from the historical empty weight fractions and
+/// the Breguet range equation (Raymer chapter 3). The empty weight is split
+/// between the groups by w_e_fraction_<group>, each group at
+/// x_cg_fraction_<group> of the fuselage length, and the fuel sits at
+/// x_cg_fraction_fuel of it, none of which has a default.
+pub struct ClassOne {
+    aircraft_type: AircraftType,
+    w_payload: f64, // payload including the crew (lb)
+    range: f64, // design range (nmi)
+    l_d: f64, // cruise lift to drag ratio
+    c: f64, // cruise specific fuel consumption (1/hr)
+    v: f64, // cruise speed (knots)
+    w_dg: f64, // given maximum take-off weight, sized from payload and range otherwise (lb)
+    groups: Vec<(&'static str, f64, f64)>, // empty weight fraction and CG as fraction of the fuselage length
+    x_cg_fuel: f64, // fuel CG as fraction of the fuselage length
+}
+
+impl ClassOne {
+    pub fn new(params: &Params) -> Result<Self, String> {
+        let aircraft_type = AircraftType::from_code(
+            *params.get("class_one_type").expect("missing class_one_type")
+        )?;
+        let (l_d, c, v) = aircraft_type.cruise();
+        let get = |symbol: String| params.get(&symbol).copied().ok_or_else(|| format!("missing {}", symbol));
+        let mut groups = vec![];
+        for group in GROUPS {
+            let name = group.replace(' ', "_");
+            groups.push((group, get(format!("w_e_fraction_{}", name))?, get(format!("x_cg_fraction_{}", name))?));
+        }
+        let total: f64 = groups.iter().map(|(_, fraction, _)| fraction).sum();
+        if (total - 1.).abs() > 1e-6 {
+            return Err(format!("the empty weight fractions of the groups add up to {} instead of 1", total));
+        }
+        let mut class_one = Self {
+            aircraft_type,
+            w_payload: *params.get("w_payload").expect("missing w_payload"),
+            range: *params.get("range").expect("missing range"),
+            l_d: params.get("l_d_cruise").copied().unwrap_or(l_d),
+            c: params.get("c_cruise").copied().unwrap_or(c),
+            v: params.get("v_cruise").copied().unwrap_or(v),
+            w_dg: 0.,
+            groups,
+            x_cg_fuel: get("x_cg_fraction_fuel".to_string())?,
+        };
+        class_one.w_dg = match params.get("mtow") {
+            Some(mtow) => *mtow,
+            None => class_one.size()?,
+        };
+        Ok(class_one)
+    }
+
+    /// Weight fraction at the end of the cruise: warmup and take-off, climb
+    /// and Breguet cruise.
+    fn cruise_end_fraction(&self) -> f64 {
+        0.970 * 0.985 * f64::exp(-self.range * self.c / (self.v * self.l_d))
+    }
+
+    /// Mission fuel fraction W_f/W_0, with 6% reserve and trapped fuel.
+    pub fn fuel_fraction(&self) -> f64 {
+        1.06 * (1. - self.cruise_end_fraction() * 0.995)
+    }
+
+    /// Design gross weight closing the mission (lb),
+    /// W_0 = W_payload / (1 - W_f/W_0 - W_e/W_0).
+    fn size(&self) -> Result<f64, String> {
+        let mut w_0 = 10. * self.w_payload;
+        for _ in 0..SIZING_ITERATIONS {
+            let available = 1. - self.fuel_fraction() - self.aircraft_type.empty_weight_fraction(w_0);
+            if available <= 0. {
+                return Err("no design gross weight closes the mission".to_string());
+            }
+            w_0 = self.w_payload / available;
+        }
+        Ok(w_0)
+    }
+
+    /// Design gross weight closing the mission around a given empty weight
+    /// (lb), W_0 = (W_e + W_payload) / (1 - W_f/W_0).
+    pub fn gross_weight(&self, w_e: f64) -> f64 {
+        (w_e + self.w_payload) / (1. - self.fuel_fraction())
+    }
+
+    /// Design gross weight (lb), the given MTOW or the one closing the mission.
+    pub fn w_dg(&self) -> f64 {
+        self.w_dg
+    }
+
+    /// Empty weight (lb).
+    pub fn w_e(&self) -> f64 {
+        let w_0 = self.w_dg();
+        self.aircraft_type.empty_weight_fraction(w_0) * w_0
+    }
+
+    /// Mission fuel (lb).
+    pub fn w_fuel(&self) -> f64 {
+        self.fuel_fraction() * self.w_dg()
+    }
+
+    /// Landing weight at the end of the cruise (lb).
+    pub fn w_l(&self) -> f64 {
+        self.cruise_end_fraction() * self.w_dg()
+    }
+
+    /// Weight left at MTOW once empty weight, payload and fuel are loaded,
+    /// negative when the given MTOW is too small for the range (lb).
+    pub fn margin(&self) -> f64 {
+        self.w_dg() - self.w_e() - self.w_payload - self.w_fuel()
+    }
+
+    /// Fuselage length (ft).
+    pub fn l(&self) -> f64 {
+        self.aircraft_type.fuselage_length(self.w_dg())
+    }
+
+    /// Empty weight of each group (lb) and its horizontal CG from the nose (ft).
+    pub fn groups(&self) -> Vec<(&'static str, f64, f64)> {
+        let (w_e, l) = (self.w_e(), self.l());
+        self.groups.iter()
+            .map(|(name, fraction, x)| (*name, fraction * w_e, x * l))
+            .collect()
+    }
+
+    /// Horizontal CG of the empty aircraft from the nose (ft).
+    pub fn x_cg_empty(&self) -> f64 {
+        let groups = self.groups();
+        groups.iter().map(|(_, w, x)| w * x).sum::<f64>() /
+        groups.iter().map(|(_, w, _)| w).sum::<f64>()
+    }
+
+    /// Class II parameters the class I estimate can stand in for.
+    pub fn seeds(&self) -> Vec<(&'static str, f64)> {
+        let l = self.l();
+        vec![
+            ("w_dg", self.w_dg()),
+            ("w_l", self.w_l()),
+            ("w_mzf", self.w_e() + self.w_payload),
+            ("w_fuel", self.w_fuel()),
+            ("l", l),
+            ("x_cg_fuel", self.x_cg_fuel * l),
+        ]
+    }
+
+    /// Fills the class II parameters which are not given.
+    pub fn seed(&self, params: &mut Params) {
+        for (symbol, value) in self.seeds() {
+            params.entry(symbol.to_string()).or_insert(value);
+        }
+    }
+}
+
+/// Writes the seeds in the format of the parameters files.
+pub fn write_seeds(path: &str, class_one: &ClassOne) -> Result<(), Box<dyn Error>> {
+    let mut writer = Writer::from_path(path)?;
+    writer.write_record(["symbol", "value"])?;
+    for (symbol, value) in class_one.seeds() {
+        writer.write_record([symbol.to_string(), value.to_string()])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
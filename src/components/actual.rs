@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use crate::Params;
+use crate::components::Item;
+use crate::utils::point::Point;
+use crate::utils::weight::Weight;
+
+/// Measured or vendor weights replacing the estimates. For an item, with
+/// its name in underscores and without the side:
+/// - w_actual_<item>: actual weight of both sides together (lb)
+/// - x/y/z_cg_actual_<item>: actual CG, of the right side for a pair, not
+///   applied to a centreline item
+/// - k_calibration_<item>: factor scaling the estimate instead
+/// - calibrate_<item>: 1 to derive k_calibration_<item> from w_actual_<item>
+pub struct ActualWeights {
+    params: Params,
+    calibrations: HashMap<String, f64>, // factors derived from the actual weights
+}
+
+impl ActualWeights {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            params: params.iter()
+                .filter(|(symbol, _)| {
+                    symbol.starts_with("w_actual_") ||
+                    symbol.contains("_cg_actual_") ||
+                    symbol.starts_with("k_calibration_") ||
+                    symbol.starts_with("calibrate_")
+                })
+                .map(|(symbol, value)| (symbol.clone(), *value))
+                .collect(),
+            calibrations: HashMap::new(),
+        }
+    }
+
+    /// Checks each item to calibrate has its actual weight.
+    pub fn check(params: &Params) -> Result<(), String> {
+        for (symbol, value) in params {
+            if let Some(name) = symbol.strip_prefix("calibrate_") {
+                if *value == 1. && !params.contains_key(&format!("w_actual_{}", name)) {
+                    return Err(format!("{} needs w_actual_{}", symbol, name));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn get(&self, prefix: &str, item: &Item) -> Option<f64> {
+        self.params.get(&format!("{}_{}", prefix, key(item))).copied()
+    }
+
+    fn calibrated(&self, item: &Item) -> bool {
+        self.get("calibrate", item) == Some(1.)
+    }
+
+    fn calibration(&self, item: &Item) -> Option<f64> {
+        self.calibrations.get(&key(item)).copied().or_else(|| self.get("k_calibration", item))
+    }
+
+    fn cg(&self, item: &Item) -> Option<Point<f64>> {
+        let x = self.get("x_cg_actual", item)?;
+        let y = self.get("y_cg_actual", item)?;
+        let z = self.get("z_cg_actual", item)?;
+        if item.name().ends_with(" centre") {
+            return None;
+        }
+        let side = if item.name().ends_with(" left") { -1. } else { 1. };
+        Some(Point::new(x, side * y, z))
+    }
+
+    /// Replaces the estimates of the items of a group by their actual
+    /// weights or scales them by their calibration factors, the actual
+    /// weight being split between the items sharing it as the estimate.
+    pub fn apply(&self, items: Vec<Item>) -> Vec<Item> {
+        let mut estimates: HashMap<String, (f64, f64)> = HashMap::new(); // estimate and number of items
+        for item in &items {
+            let estimate = estimates.entry(key(item)).or_insert((0., 0.));
+            estimate.0 += item.weight().get_val();
+            estimate.1 += 1.;
+        }
+        items.into_iter()
+            .map(|item| {
+                let item = match self.cg(&item) {
+                    Some(cg) => item.with_cg(cg),
+                    None => item,
+                };
+                if let Some(k) = self.calibration(&item) {
+                    item.with_calibration(k)
+                } else if let (false, Some(w_actual)) = (self.calibrated(&item), self.get("w_actual", &item)) {
+                    let (estimate, n) = estimates[&key(&item)];
+                    let share = if estimate != 0. { item.weight().get_val() / estimate } else { 1. / n };
+                    item.with_actual(Weight::new(share * w_actual))
+                } else {
+                    item
+                }
+            })
+            .collect()
+    }
+
+    /// Derives the calibration factors from the estimated items, each
+    /// factor matching the estimate of both sides to the actual weight.
+    pub fn calibrate(&mut self, items: &[Item]) -> Vec<(String, f64)> {
+        let mut estimates: HashMap<String, f64> = HashMap::new();
+        for item in items.iter().filter(|item| self.calibrated(item)) {
+            *estimates.entry(key(item)).or_insert(0.) += item.weight().get_val();
+        }
+        let mut factors: Vec<(String, f64)> = estimates.into_iter()
+            .map(|(name, estimate)| {
+                let w_actual = self.params[&format!("w_actual_{}", name)]; // given, see check
+                (name, w_actual / estimate)
+            })
+            .collect();
+        factors.sort_by(|a, b| a.0.cmp(&b.0));
+        self.calibrations.extend(factors.iter().cloned());
+        factors
+    }
+}
+
+fn key(item: &Item) -> String {
+    item.base_name().replace(' ', "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn actual_weight_split_as_estimate() {
+        let params = Params::from([("w_actual_engines".to_string(), 3000.)]);
+        let cg = Point::new(0., 0., 0.);
+        let items = ActualWeights::new(&params).apply(vec![
+            Item::point("engines left", Weight::new(500.), cg),
+            Item::point("engines right", Weight::new(500.), cg),
+            Item::point("engines centre", Weight::new(1000.), cg),
+        ]);
+        let weights: Vec<f64> = items.iter().map(|item| item.weight().get_val()).collect();
+        assert_eq!(weights, [750., 750., 1500.]);
+    }
+}
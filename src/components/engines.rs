@@ -0,0 +1,228 @@
+use crate::utils::point::Point;
+use crate::utils::weight::Weight;
+use crate::Params;
+use crate::components::Item;
+use crate::components::method::Method;
+
+pub struct Engines {
+    installed_engines: InstalledEngines,
+    thrust_reversers: ThrustReversers,
+    oil_system: OilSystem,
+    engine_pneumatic_starter: EnginePneumaticStarter,
+    propellers: Option<Propellers>, // turboprops only
+    n_en: usize, // Number of engines, rounded as sweeps and perturbations may not give an integer
+    cg: Point<f64>, // CG of the right engines, the left ones are symmetric
+    centre_cg: Option<Point<f64>>, // CG of the centreline engine, odd number of engines only
+}
+
+impl Engines {
+    pub fn new(params: &Params) -> Self {
+        let n_en = params.get("n_en").expect("missing n_en").round() as usize;
+        Self {
+            installed_engines: InstalledEngines::new(params),
+            thrust_reversers: ThrustReversers::new(params),
+            oil_system: OilSystem::new(params),
+            engine_pneumatic_starter: EnginePneumaticStarter::new(params),
+            propellers: params.contains_key("d_p").then(|| Propellers::new(params)),
+            cg: Point::new(
+                *params.get("x_cg_engine").expect("missing x_cg_engine"),
+                *params.get("y_cg_engine").expect("missing y_cg_engine"),
+                *params.get("z_cg_engine").expect("missing z_cg_engine"),
+            ),
+            n_en,
+            centre_cg: (n_en % 2 == 1).then(|| Point::new(
+                *params.get("x_cg_engine_centre").expect("missing x_cg_engine_centre"),
+                0.,
+                *params.get("z_cg_engine_centre").expect("missing z_cg_engine_centre"),
+            )),
+        }
+    }
+
+    pub fn weight(&self) -> Weight {
+        self.items().into_iter().fold(Weight::new(0.), |r, item| r + item.weight())
+    }
+
+    pub fn pos_times_weight(&self) -> Point<f64> {
+        self.items().iter().fold(Point::new(0., 0., 0.), |r, item| r + item.pos_times_weight())
+    }
+
+    /// Weight of one engine with its contents (lb), as used by the nacelle.
+    pub fn w_ec(&self) -> f64 {
+        (
+            self.installed_engines.weight().get_val() +
+            self.thrust_reversers.weight().get_val()
+        ) / self.installed_engines.n_en
+    }
+
+    /// Every item is split between the engines, those of an odd number of
+    /// engines but one being on the left and right, the last on the
+    /// centreline.
+    pub fn items(&self) -> Vec<Item> {
+        let mut items = vec![
+            (
+                ["engines left", "engines right", "engines centre"],
+                self.installed_engines.weight(),
+                Some(Method::Raymer),
+            ),
+            (
+                ["thrust reversers left", "thrust reversers right", "thrust reversers centre"],
+                self.thrust_reversers.weight(),
+                Some(Method::Raymer),
+            ),
+            (["oil system left", "oil system right", "oil system centre"], self.oil_system.weight(), None),
+            (
+                ["engine pneumatic starter left", "engine pneumatic starter right", "engine pneumatic starter centre"],
+                self.engine_pneumatic_starter.weight(),
+                Some(Method::Raymer),
+            ),
+        ];
+        if let Some(propellers) = &self.propellers {
+            items.push((
+                ["propellers left", "propellers right", "propellers centre"],
+                propellers.weight(),
+                Some(Method::GeneralDynamics),
+            ));
+        }
+        let side_fraction = (self.n_en / 2) as f64 / self.n_en as f64; // of the weight on each side
+        let left = Point::new(*self.cg.x(), -self.cg.y(), *self.cg.z());
+        items.into_iter()
+            .flat_map(|([name_left, name_right, name_centre], weight, method)| {
+                let tag = |item: Item| match method {
+                    Some(method) => item.with_method(method),
+                    None => item,
+                };
+                let side = Weight::new(side_fraction * weight.get_val());
+                let centre = Weight::new((1. - 2. * side_fraction) * weight.get_val());
+                let mut items = vec![];
+                if side_fraction > 0. {
+                    items.push(tag(Item::point(name_left, side, left)));
+                    items.push(tag(Item::point(name_right, side, self.cg)));
+                }
+                if let Some(cg) = self.centre_cg {
+                    items.push(tag(Item::point(name_centre, centre, cg)));
+                }
+                items
+            })
+            .collect()
+    }
+}
+
+//---------------------------------------------------- installed engines
+
+struct InstalledEngines {
+    n_en: f64, // Number of engines
+    w_en: f64, // Engine weight (lb)
+    k_p: f64, // 1.4 for engine with propeller, 1.0 otherwise
+}
+
+impl InstalledEngines {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            n_en: *params.get("n_en").expect("missing n_en"),
+            w_en: *params.get("w_en").expect("missing w_en"),
+            k_p: *params.get("k_p").expect("missing k_p"),
+        }
+    }
+
+    /// Engines with their accessories, without reversers (Raymer's W_ec with K_tr = 1).
+    pub fn weight(&self) -> Weight {
+        Weight::new(self.n_en * 2.331 * self.w_en.powf(0.901) * self.k_p)
+    }
+}
+
+//---------------------------------------------------- thrust reversers
+
+struct ThrustReversers {
+    n_en: f64, // Number of engines
+    w_en: f64, // Engine weight (lb)
+    k_p: f64, // 1.4 for engine with propeller, 1.0 otherwise
+    k_tr: f64, // 1.18 for jet with thrust reverser, 1.0 otherwise
+}
+
+impl ThrustReversers {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            n_en: *params.get("n_en").expect("missing n_en"),
+            w_en: *params.get("w_en").expect("missing w_en"),
+            k_p: *params.get("k_p").expect("missing k_p"),
+            k_tr: *params.get("k_tr").expect("missing k_tr"),
+        }
+    }
+
+    /// Increase of Raymer's W_ec due to K_tr.
+    pub fn weight(&self) -> Weight {
+        Weight::new(self.n_en * 2.331 * self.w_en.powf(0.901) * self.k_p * (self.k_tr - 1.))
+    }
+}
+
+//---------------------------------------------------- oil system
+
+struct OilSystem {
+    n_en: f64, // Number of engines
+    w_en: f64, // Engine weight (lb)
+    k_oil: f64, // oil system and cooler weight as a fraction of the engine weight
+}
+
+impl OilSystem {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            n_en: *params.get("n_en").expect("missing n_en"),
+            w_en: *params.get("w_en").expect("missing w_en"),
+            k_oil: *params.get("k_oil").expect("missing k_oil"),
+        }
+    }
+
+    pub fn weight(&self) -> Weight {
+        Weight::new(self.k_oil * self.n_en * self.w_en)
+    }
+}
+
+//---------------------------------------------------- engine pneumatic starter
+
+struct EnginePneumaticStarter {
+    n_en: f64, // Number of engines
+    w_en: f64, // Engine weight (lb)
+}
+
+impl EnginePneumaticStarter {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            n_en: *params.get("n_en").expect("missing n_en"),
+            w_en: *params.get("w_en").expect("missing w_en"),
+        }
+    }
+
+    pub fn weight(&self) -> Weight {
+        Weight::new(49.19 * f64::powf(self.n_en * self.w_en * 1e-3, 0.541))
+    }
+}
+
+//---------------------------------------------------- propellers
+
+struct Propellers {
+    n_en: f64, // Number of engines
+    n_bl: f64, // Number of blades per propeller
+    d_p: f64, // Propeller diameter (ft)
+    p_to: f64, // Take-off shaft power per engine (shp)
+}
+
+impl Propellers {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            n_en: *params.get("n_en").expect("missing n_en"),
+            n_bl: *params.get("n_bl").expect("missing n_bl"),
+            d_p: *params.get("d_p").expect("missing d_p"),
+            p_to: *params.get("p_to").expect("missing p_to"),
+        }
+    }
+
+    /// General Dynamics (Roskam), turboprop propellers
+    pub fn weight(&self) -> Weight {
+        Weight::new(
+            31.92 *
+            self.n_en *
+            self.n_bl.powf(0.391) *
+            f64::powf(self.d_p * self.p_to * 1e-3, 0.782)
+        )
+    }
+}
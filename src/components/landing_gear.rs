@@ -0,0 +1,212 @@
+use crate::utils::point::Point;
+use crate::utils::weight::Weight;
+use crate::Params;
+use crate::components::Item;
+use crate::components::method::Method;
+use crate::components::technology::{Technology, Structure};
+use crate::utils::in_to_ft;
+
+pub struct LandingGear {
+    main_landing_gear: MainLandingGear,
+    nose_landing_gear: NoseLandingGear,
+}
+
+impl LandingGear {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            main_landing_gear: MainLandingGear::new(params),
+            nose_landing_gear: NoseLandingGear::new(params),
+        }
+    }
+
+    pub fn weight(&self, w_dg: f64) -> Weight {
+        self.main_landing_gear.weight(w_dg) +
+        self.nose_landing_gear.weight(w_dg)
+    }
+
+    pub fn pos_times_weight(&self, w_dg: f64) -> Point<f64> {
+        self.main_landing_gear.cg() * self.main_landing_gear.weight(w_dg).get_val() +
+        self.nose_landing_gear.cg() * self.nose_landing_gear.weight(w_dg).get_val()
+    }
+
+    pub fn items(&self, w_dg: f64) -> Vec<Item> {
+        vec![
+            Item::point(
+                "main landing gear",
+                self.main_landing_gear.weight(w_dg),
+                self.main_landing_gear.cg(),
+            ).with_method(self.main_landing_gear.method)
+            .with_technology(self.main_landing_gear.technology.factor()),
+            Item::point(
+                "nose landing gear",
+                self.nose_landing_gear.weight(w_dg),
+                self.nose_landing_gear.cg(),
+            ).with_method(self.nose_landing_gear.method)
+            .with_technology(self.nose_landing_gear.technology.factor()),
+        ]
+    }
+
+    pub fn set_method(&mut self, method: Method) {
+        self.main_landing_gear.method = method;
+        self.nose_landing_gear.method = method;
+    }
+}
+
+//------------------------------------------ alternative methods
+
+// Torenbeek's coefficients A, B, C, D for retractable gears of jet transports
+const TORENBEEK_MAIN: [f64; 4] = [33., 0.04, 0.021, 0.];
+const TORENBEEK_NOSE: [f64; 4] = [12., 0.06, 0., 0.];
+
+/// Torenbeek, K_gr (A + B W^0.75 + C W + D W^1.5)
+fn torenbeek(coefficients: [f64; 4], k_gr: f64, w_dg: f64) -> f64 {
+    let [a, b, c, d] = coefficients;
+    k_gr * (a + b * w_dg.powf(0.75) + c * w_dg + d * w_dg.powf(1.5))
+}
+
+/// Share of the whole gear carried by the main gear, used to split the
+/// methods which only give the total, in Torenbeek's proportions.
+fn main_gear_share(w_dg: f64) -> f64 {
+    let main = torenbeek(TORENBEEK_MAIN, 1., w_dg);
+    main / (main + torenbeek(TORENBEEK_NOSE, 1., w_dg))
+}
+
+/// General Dynamics, whole gear
+fn general_dynamics(w_dg: f64) -> f64 {
+    62.21 * f64::powf(w_dg * 1e-3, 0.84)
+}
+
+/// USAF, whole gear
+/// - l_m: main landing gear length (inches)
+fn usaf(w_l: f64, n_l: f64, l_m: f64) -> f64 {
+    0.054 * in_to_ft(l_m).powf(0.501) * f64::powf(w_l * n_l, 0.684)
+}
+
+//------------------------------------------ main landing gear
+
+pub struct MainLandingGear {
+    w_l: f64, // Landing design gross weight (lb)
+    n_l: f64, // Ultimate landing gear load factor. 1.5 × N_gear
+    k_mp: f64, // 1.126 for kneeling main gear; 1.0 otherwise
+    l_m: f64, // Main landing gear length (inches)
+    n_mw: f64, // Number of main wheels
+    v_s: f64, // Landing stall speed (ft/s)
+    n_mss: f64, // Number of main gear shock struts
+    cg: Point<f64>, // center of gravity of main landing gear
+    // alternative methods
+    method: Method,
+    technology: Technology,
+    k_gr: f64, // 1.08 for high wing, 1.0 for low wing
+}
+
+
+impl MainLandingGear {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            w_l: *params.get("w_l").expect("missing w_l"),
+            n_l: *params.get("n_l").expect("missing n_l"),
+            k_mp: *params.get("k_mp").expect("missing k_mp"),
+            l_m: *params.get("l_m").expect("missing l_m"),
+            n_mw: *params.get("n_mw").expect("missing n_mw"),
+            v_s: *params.get("v_s").expect("missing v_s"),
+            n_mss: *params.get("n_mss").expect("missing n_mss"),
+            cg: Point::new(
+                *params.get("x_cg_main_landing_gear").expect("missing x_cg_main_landing_gear"),
+                *params.get("y_cg_main_landing_gear").expect("missing y_cg_main_landing_gear"),
+                *params.get("z_cg_main_landing_gear").expect("missing z_cg_main_landing_gear"),
+            ),
+            // alternative methods, Raymer unless chosen
+            method: Method::from_code(params.get("method_main_landing_gear").copied().unwrap_or(0.))
+                .expect("invalid method_main_landing_gear"),
+            technology: Technology::new(params, "main_landing_gear", Structure::LandingGear),
+            k_gr: params.get("k_gr").copied().unwrap_or(1.),
+        }
+    }
+
+    /// - w_dg: design gross weight in lb
+    pub fn weight(&self, w_dg: f64) -> Weight {
+        Weight::new(self.technology.factor() * self.method.estimate(
+            || self.raymer(),
+            || torenbeek(TORENBEEK_MAIN, self.k_gr, w_dg),
+            || main_gear_share(w_dg) * general_dynamics(w_dg),
+            || main_gear_share(w_dg) * usaf(self.w_l, self.n_l, self.l_m),
+        ))
+    }
+
+    fn raymer(&self) -> f64 {
+        0.0106 *
+        self.k_mp *
+        self.w_l.powf(0.888) *
+        self.n_l.powf(0.25) *
+        self.l_m.powf(0.4) *
+        self.n_mw.powf(0.321) *
+        self.v_s.powf(0.1) /
+        self.n_mss.powf(0.5)
+    }
+
+    pub fn cg(&self) -> Point<f64> {
+        self.cg
+    }
+}
+
+//------------------------------------------ nose landing gear
+
+pub struct NoseLandingGear {
+    w_l: f64, // Landing design gross weight (lb)
+    n_l: f64, // Ultimate landing gear load factor. 1.5 × N_gear
+    k_np: f64, // 1.15 for kneeling nose-gear; 1.0 otherwise
+    l_n: f64, // Nose landing gear length (inches)
+    n_nw: f64, // Number of nose wheels
+    cg: Point<f64>, // center of gravity of main landing gear
+    // alternative methods
+    method: Method,
+    technology: Technology,
+    k_gr: f64, // 1.08 for high wing, 1.0 for low wing
+    l_m: f64, // Main landing gear length (inches)
+}
+
+impl NoseLandingGear {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            w_l: *params.get("w_l").expect("missing w_l"),
+            n_l: *params.get("n_l").expect("missing n_l"),
+            k_np: *params.get("k_np").expect("missing k_np"),
+            l_n: *params.get("l_n").expect("missing l_n"),
+            n_nw: *params.get("n_nw").expect("missing n_nw"),
+            cg: Point::new(
+                *params.get("x_cg_nose_landing_gear").expect("missing x_cg_nose_landing_gear"),
+                *params.get("y_cg_nose_landing_gear").expect("missing y_cg_nose_landing_gear"),
+                *params.get("z_cg_nose_landing_gear").expect("missing z_cg_nose_landing_gear"),
+            ),
+            // alternative methods, Raymer unless chosen
+            method: Method::from_code(params.get("method_nose_landing_gear").copied().unwrap_or(0.))
+                .expect("invalid method_nose_landing_gear"),
+            technology: Technology::new(params, "nose_landing_gear", Structure::LandingGear),
+            k_gr: params.get("k_gr").copied().unwrap_or(1.),
+            l_m: *params.get("l_m").expect("missing l_m"),
+        }
+    }
+
+    /// - w_dg: design gross weight in lb
+    pub fn weight(&self, w_dg: f64) -> Weight {
+        Weight::new(self.technology.factor() * self.method.estimate(
+            || self.raymer(),
+            || torenbeek(TORENBEEK_NOSE, self.k_gr, w_dg),
+            || (1. - main_gear_share(w_dg)) * general_dynamics(w_dg),
+            || (1. - main_gear_share(w_dg)) * usaf(self.w_l, self.n_l, self.l_m),
+        ))
+    }
+
+    fn raymer(&self) -> f64 {
+        0.032 *
+        self.k_np *
+        self.w_l.powf(0.646) *
+        self.n_l.powf(0.2) *
+        self.l_n.powf(0.5) *
+        self.n_nw.powf(0.45)
+    }
+
+    pub fn cg(&self) -> Point<f64> {
+        self.cg
+    }
+}
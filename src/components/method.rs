@@ -0,0 +1,69 @@
+/// Class II weight estimation method of a component. Roskam (Airplane
+/// Design Part V) has no equations of his own for transports: his class II
+/// procedure gives the General Dynamics and Torenbeek ones, which are
+/// selected as such.
+#[derive(Copy, Clone, PartialEq)]
+pub enum Method {
+    Raymer,
+    Torenbeek,
+    GeneralDynamics,
+    Usaf,
+}
+
+impl Method {
+    pub const ALL: [Method; 4] = [
+        Method::Raymer,
+        Method::Torenbeek,
+        Method::GeneralDynamics,
+        Method::Usaf,
+    ];
+
+    /// 0 for Raymer, 1 for Torenbeek, 2 for General Dynamics, 3 for USAF
+    pub fn from_code(code: f64) -> Result<Self, String> {
+        let method = match code as i32 {
+            0 => Method::Raymer,
+            1 => Method::Torenbeek,
+            2 => Method::GeneralDynamics,
+            3 => Method::Usaf,
+            4 => return Err("Roskam's transport equations are the Torenbeek (1) and General Dynamics (2) ones".into()),
+            _ => return Err(format!("unknown weight method {}, expected 0 to 3", code)),
+        };
+        Ok(method)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Method::Raymer => "Raymer",
+            Method::Torenbeek => "Torenbeek",
+            Method::GeneralDynamics => "General Dynamics",
+            Method::Usaf => "USAF",
+        }
+    }
+
+    /// Optional parameters the equations of the method need, on top of the
+    /// ones every method uses.
+    pub fn inputs(&self) -> &'static [&'static str] {
+        match self {
+            Method::Raymer => &[],
+            Method::Torenbeek => &["v_d", "w_mzf", "k_h"],
+            Method::GeneralDynamics => &["v_d", "m_h", "t_c_ratio_root_h", "s_r", "lambda_v"],
+            Method::Usaf => &["v_c", "v_h", "t_c_ratio_root_h", "lambda_v"],
+        }
+    }
+
+    /// Picks the equation of the method.
+    pub fn estimate(
+        &self,
+        raymer: impl Fn() -> f64,
+        torenbeek: impl Fn() -> f64,
+        general_dynamics: impl Fn() -> f64,
+        usaf: impl Fn() -> f64,
+    ) -> f64 {
+        match self {
+            Method::Raymer => raymer(),
+            Method::Torenbeek => torenbeek(),
+            Method::GeneralDynamics => general_dynamics(),
+            Method::Usaf => usaf(),
+        }
+    }
+}
@@ -2,9 +2,111 @@ pub mod wings;
 pub mod tailplane;
 pub mod varying;
 pub mod fuselage;
+pub mod engines;
+pub mod landing_gear;
+pub mod systems;
+pub mod method;
+pub mod technology;
+pub mod actual;
 use crate::Params;
 use crate::utils::point::Point;
 use crate::utils::weight::Weight;
+use crate::aerodynamics::planform::Planform;
+use crate::components::method::Method;
+use crate::components::actual::ActualWeights;
+
+/// Weight and CG of one item of a group.
+#[derive(Clone)]
+pub struct Item {
+    name: &'static str,
+    weight: Weight,
+    cg: Point<f64>,
+    extent: Point<f64>, // dimensions of the item taken as a uniform box, zero for a point mass (ft)
+    method: Option<Method>, // weight estimation method, none for loads and given weights
+    technology: f64, // technology factor included in the weight, 1.0 for none
+    estimate: Option<Weight>, // estimated weight when replaced by an actual or calibrated one
+    calibration: Option<f64>, // calibration factor applied to the estimate
+}
+
+impl Item {
+    pub fn point(name: &'static str, weight: Weight, cg: Point<f64>) -> Self {
+        Self::distributed(name, weight, cg, Point::new(0., 0., 0.))
+    }
+
+    pub fn distributed(name: &'static str, weight: Weight, cg: Point<f64>, extent: Point<f64>) -> Self {
+        Self { name, weight, cg, extent, method: None, technology: 1., estimate: None, calibration: None }
+    }
+
+    pub fn with_method(mut self, method: Method) -> Self {
+        self.method = Some(method);
+        self
+    }
+
+    pub fn with_technology(mut self, factor: f64) -> Self {
+        self.technology = factor;
+        self
+    }
+
+    /// Replaces the estimated weight, which is kept for comparison.
+    pub fn with_actual(mut self, weight: Weight) -> Self {
+        self.estimate = Some(self.weight);
+        self.weight = weight;
+        self
+    }
+
+    pub fn with_calibration(mut self, k: f64) -> Self {
+        self.estimate = Some(self.weight);
+        self.weight = Weight::new(k * self.weight.get_val());
+        self.calibration = Some(k);
+        self
+    }
+
+    pub fn with_cg(mut self, cg: Point<f64>) -> Self {
+        self.cg = cg;
+        self
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Name shared by the left, right and centreline items of a group.
+    pub fn base_name(&self) -> &'static str {
+        self.name.trim_end_matches(" left").trim_end_matches(" right").trim_end_matches(" centre")
+    }
+
+    pub fn weight(&self) -> Weight {
+        self.weight
+    }
+
+    pub fn cg(&self) -> Point<f64> {
+        self.cg
+    }
+
+    pub fn extent(&self) -> Point<f64> {
+        self.extent
+    }
+
+    pub fn method(&self) -> Option<Method> {
+        self.method
+    }
+
+    pub fn technology(&self) -> f64 {
+        self.technology
+    }
+
+    pub fn estimate(&self) -> Option<Weight> {
+        self.estimate
+    }
+
+    pub fn calibration(&self) -> Option<f64> {
+        self.calibration
+    }
+
+    pub fn pos_times_weight(&self) -> Point<f64> {
+        self.cg * self.weight.get_val()
+    }
+}
 
 pub trait Component {
     fn new(params: &Params) -> Self;
@@ -14,8 +116,6 @@ pub trait Component {
     fn cg(self) -> Option<Point<f64>>;
 }
 
-/*
-
 use crate::components::{
     wings::Wings,
     tailplane::Tailplane,
@@ -24,13 +124,16 @@ use crate::components::{
     landing_gear::LandingGear,
     systems::Systems,
 };
+
 pub struct Aircraft {
     wings: Wings,
     tailplane: Tailplane,
     engines: Engines,
     fuselage: Fuselage,
     landing_gear: LandingGear,
-    systems: Systems
+    systems: Systems,
+    wings_root_pos: Point<f64>, // position of the root of the wing
+    actual: ActualWeights,
 }
 
 impl Aircraft {
@@ -49,18 +152,66 @@ impl Aircraft {
             fuselage,
             landing_gear,
             systems,
+            wings_root_pos: Planform::wing(params).root(),
+            actual: ActualWeights::new(params),
         }
     }
 
-    pub fn weight(self, w_dg: f64, n_z: f64) -> f64 {
-        let mut r = self.wings.weight(w_dg, n_z);
-        r += self.tailplane.weight(w_dg, n_z);
-        r += self.engines.weight(n_z);
-        r += self.fuselage.weight(w_dg, n_z);
-        r += self.landing_gear.weight();
-        r += self.systems.weight(w_dg);
-        if r < 0. { panic!("negative weight"); }
+    /// - w_dg: design gross weight in lb
+    pub fn weight(&self, w_dg: f64) -> Weight {
+        let r = self.items(w_dg).into_iter().fold(Weight::new(0.), |r, item| r + item.weight());
+        if r.get_val() < 0. { panic!("negative weight"); }
         r
     }
+
+    /// Items of each group, for the group weight statement.
+    pub fn groups(&self, w_dg: f64) -> Vec<(&'static str, Vec<Item>)> {
+        self.estimated_groups(w_dg).into_iter()
+            .map(|(group, items)| (group, self.actual.apply(items)))
+            .collect()
+    }
+
+    /// Items of each group as estimated, without the actual weights.
+    pub fn estimated_groups(&self, w_dg: f64) -> Vec<(&'static str, Vec<Item>)> {
+        vec![
+            ("wings", self.wings.items(w_dg, self.wings_root_pos)),
+            ("tailplane", self.tailplane.items(w_dg)),
+            ("engines", self.engines.items()),
+            ("fuselage", self.fuselage.items(w_dg)),
+            ("landing gear", self.landing_gear.items(w_dg)),
+            ("systems", self.systems.items(w_dg)),
+        ]
+    }
+
+    pub fn items(&self, w_dg: f64) -> Vec<Item> {
+        self.groups(w_dg).into_iter().flat_map(|(_, items)| items).collect()
+    }
+
+    /// Derives the calibration factors of the items to calibrate from their
+    /// current estimates, returned as k_calibration_<item> parameters.
+    pub fn calibrate(&mut self, w_dg: f64) -> Vec<(String, f64)> {
+        let items: Vec<Item> = self.estimated_groups(w_dg).into_iter().flat_map(|(_, items)| items).collect();
+        self.actual.calibrate(&items).into_iter()
+            .map(|(name, k)| (format!("k_calibration_{}", name), k))
+            .collect()
+    }
+
+    /// - i_y: yawing moment of inertia (lb.ft2)
+    pub fn set_i_y(&mut self, i_y: f64) {
+        self.systems.set_i_y(i_y);
+    }
+
+    /// Applies the same class II method to every component which has alternatives.
+    pub fn set_method(&mut self, method: Method) {
+        self.wings.set_method(method);
+        self.tailplane.set_method(method);
+        self.fuselage.set_method(method);
+        self.landing_gear.set_method(method);
+    }
+
+    /// - k_y: aircraft pitching radius of gyration (ft)
+    /// - k_z: aircraft yaw radius of gyration (ft)
+    pub fn set_radii_of_gyration(&mut self, k_y: f64, k_z: f64) {
+        self.tailplane.set_radii_of_gyration(k_y, k_z);
+    }
 }
-*/
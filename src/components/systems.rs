@@ -0,0 +1,438 @@
+use crate::utils::point::Point;
+use crate::utils::weight::Weight;
+use crate::Params;
+use crate::components::Item;
+use crate::components::method::Method;
+
+pub struct Systems {
+    hydrolic_system: HydrolicSystem,
+    furnishing: Furnishing,
+    air_conditioning: AirConditioning,
+    electrical_systems: ElectricalSystems,
+    instruments: Instruments,
+    avionics: Avionics,
+    flight_controls: FlightControls,
+    installed_apu: InstalledApu,
+    anti_icing: AntiIcing,
+    handling_gear: HandlingGear,
+}
+
+impl Systems {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            hydrolic_system: HydrolicSystem::new(params),
+            furnishing: Furnishing::new(params),
+            air_conditioning: AirConditioning::new(params),
+            electrical_systems: ElectricalSystems::new(params),
+            instruments: Instruments::new(params),
+            avionics: Avionics::new(params),
+            flight_controls: FlightControls::new(params),
+            installed_apu: InstalledApu::new(params),
+            anti_icing: AntiIcing::new(params),
+            handling_gear: HandlingGear::new(params),
+        }
+    }
+
+    pub fn weight(&self, w_dg: f64) -> Weight {
+        self.hydrolic_system.weight() +
+        self.furnishing.weight() +
+        self.air_conditioning.weight() +
+        self.electrical_systems.weight() +
+        self.instruments.weight() +
+        self.avionics.weight() +
+        self.flight_controls.weight() +
+        self.installed_apu.weight() +
+        self.anti_icing.weight(w_dg) +
+        self.handling_gear.weight(w_dg)
+    }
+
+    pub fn pos_times_weight(&self, w_dg: f64) -> Point<f64> {
+        self.hydrolic_system.cg() * self.hydrolic_system.weight().get_val() +
+        self.furnishing.cg() * self.furnishing.weight().get_val() +
+        self.air_conditioning.cg() * self.air_conditioning.weight().get_val() +
+        self.electrical_systems.cg() * self.electrical_systems.weight().get_val() +
+        self.instruments.cg() * self.instruments.weight().get_val() +
+        self.avionics.cg() * self.avionics.weight().get_val() +
+        self.flight_controls.cg() * self.flight_controls.weight().get_val() +
+        self.installed_apu.cg() * self.installed_apu.weight().get_val() +
+        self.anti_icing.cg() * self.anti_icing.weight(w_dg).get_val() +
+        self.handling_gear.cg() * self.handling_gear.weight(w_dg).get_val()
+    }
+
+    pub fn items(&self, w_dg: f64) -> Vec<Item> {
+        vec![
+            Item::point("hydraulic system", self.hydrolic_system.weight(), self.hydrolic_system.cg()),
+            Item::point("furnishing", self.furnishing.weight(), self.furnishing.cg()),
+            Item::point("air conditioning", self.air_conditioning.weight(), self.air_conditioning.cg()),
+            Item::point("electrical systems", self.electrical_systems.weight(), self.electrical_systems.cg()),
+            Item::point("instruments", self.instruments.weight(), self.instruments.cg()),
+            Item::point("avionics", self.avionics.weight(), self.avionics.cg()),
+            Item::point("flight controls", self.flight_controls.weight(), self.flight_controls.cg()),
+            Item::point("installed apu", self.installed_apu.weight(), self.installed_apu.cg()),
+            Item::point("anti icing", self.anti_icing.weight(w_dg), self.anti_icing.cg()),
+            Item::point("handling gear", self.handling_gear.weight(w_dg), self.handling_gear.cg()),
+        ]
+        .into_iter()
+        .map(|item| item.with_method(Method::Raymer))
+        .collect()
+    }
+
+    /// - i_y: yawing moment of inertia (lb.ft2)
+    pub fn set_i_y(&mut self, i_y: f64) {
+        self.flight_controls.i_y = i_y;
+    }
+}
+
+//------------------------------------------ hydrolic system
+
+struct HydrolicSystem {
+    n_f: f64,
+    l_f: f64,
+    b_w: f64,
+    cg: Point<f64>,
+}
+
+impl HydrolicSystem {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            n_f: *params.get("n_f").expect("missing n_f"),
+            l_f: *params.get("l_f").expect("missing l_f"),
+            b_w: *params.get("b_w").expect("missing b_w"),
+            cg: Point::new(
+                *params.get("x_cg_hydrolic_system").expect("missing x_cg_hydrolic_system"),
+                *params.get("y_cg_hydrolic_system").expect("missing y_cg_hydrolic_system"),
+                *params.get("z_cg_hydrolic_system").expect("missing z_cg_hydrolic_system"),
+            ),
+        }
+    }
+
+    pub fn weight(&self) -> Weight {
+        Weight::new(
+            0.2673 *
+            self.n_f *
+            f64::powf(self.l_f + self.b_w, 0.937)
+        )
+    }
+
+    pub fn cg(&self) -> Point<f64> {
+        self.cg
+    }
+}
+
+//------------------------------------------ furnishing
+
+struct Furnishing {
+    n_c: f64,
+    w_c: f64,
+    s_f: f64,
+    n_seat: f64,
+    w_seat: f64,
+    k_lav: f64,
+    n_p: f64,
+    k_buf: f64,
+    cg: Point<f64>,
+}
+
+impl Furnishing {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            n_c: *params.get("n_c").expect("missing n_c"),
+            w_c: *params.get("w_c").expect("missing w_c"),
+            s_f: *params.get("s_f").expect("missing s_f"),
+            n_seat: *params.get("n_seat").expect("missing n_seat"),
+            w_seat: *params.get("w_seat").expect("missing w_seat"),
+            k_lav: *params.get("k_lav").expect("missing k_lav"),
+            n_p: *params.get("n_p").expect("missing n_p"),
+            k_buf: *params.get("k_buf").expect("missing k_buf"),
+            cg: Point::new(
+                *params.get("x_cg_furnishing").expect("missing x_cg_furnishing"),
+                *params.get("y_cg_furnishing").expect("missing y_cg_furnishing"),
+                *params.get("z_cg_furnishing").expect("missing z_cg_furnishing"),
+            ),
+        }
+    }
+
+    pub fn weight(&self) -> Weight {
+        Weight::new(
+            0.0577 * self.n_c.powf(0.1) *
+            self.w_c.powf(0.393) * self.s_f.powf(0.75) +
+            self.n_seat * self.w_seat +
+            self.k_lav * self.n_p.powf(1.33) +
+            self.k_buf * self.n_p.powf(1.12)
+        )
+    }
+
+    pub fn cg(&self) -> Point<f64> {
+        self.cg
+    }
+}
+
+//------------------------------------------ air conditioning
+
+struct AirConditioning {
+    n_p: f64,
+    v_pr: f64,
+    w_uav: f64,
+    cg: Point<f64>,
+}
+
+impl AirConditioning {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            n_p: *params.get("n_p").expect("missing n_p"),
+            v_pr: *params.get("v_pr").expect("missing v_pr"),
+            w_uav: *params.get("w_uav").expect("missing w_uav"),
+            cg: Point::new(
+                *params.get("x_cg_air_conditioning").expect("missing x_cg_air_conditioning"),
+                *params.get("y_cg_air_conditioning").expect("missing y_cg_air_conditioning"),
+                *params.get("z_cg_air_conditioning").expect("missing z_cg_air_conditioning"),
+            ),
+        }
+    }
+
+    pub fn weight(&self) -> Weight {
+        Weight::new(
+            62.36 *
+            self.n_p.powf(0.25) *
+            f64::powf(self.v_pr * 0.001, 0.604) *
+            self.w_uav.powf(0.1)
+        )
+    }
+
+    pub fn cg(&self) -> Point<f64> {
+        self.cg
+    }
+}
+
+//------------------------------------------ electrical systems
+
+// #[derive(Clone, Copy)]
+struct ElectricalSystems {
+    r_kva: f64,
+    l_a: f64,
+    n_gen: f64,
+    cg: Point<f64>,
+}
+
+impl ElectricalSystems {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            r_kva: *params.get("r_kva").expect("missing r_kva"),
+            l_a: *params.get("l_a").expect("missing l_a"),
+            n_gen: *params.get("n_gen").expect("missing n_gen"),
+            cg: Point::new(
+                *params.get("x_cg_electrical_systems").expect("missing x_cg_electrical_systems"),
+                *params.get("y_cg_electrical_systems").expect("missing y_cg_electrical_systems"),
+                *params.get("z_cg_electrical_systems").expect("missing z_cg_electrical_systems"),
+            ),
+        }
+    }
+
+    pub fn weight(&self) -> Weight {
+        Weight::new(
+            7.291 *
+            self.r_kva.powf(0.782) *
+            self.l_a.powf(0.346) *
+            self.n_gen.powf(0.1)
+        )
+    }
+
+    pub fn cg(&self) -> Point<f64> {
+        self.cg
+    }
+}
+
+//------------------------------------------ instruments
+
+struct Instruments {
+    k_r: f64,
+    n_c: f64,
+    n_en: f64,
+    l_f: f64,
+    b_w: f64,
+    cg: Point<f64>,
+}
+
+impl Instruments {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            k_r: *params.get("k_r").expect("missing k_r"),
+            n_c: *params.get("n_c").expect("missing n_c"),
+            n_en: *params.get("n_en").expect("missing n_en"),
+            l_f: *params.get("l_f").expect("missing l_f"),
+            b_w: *params.get("b_w").expect("missing b_w"),
+            cg: Point::new(
+                *params.get("x_cg_instruments").expect("missing x_cg_instruments"),
+                *params.get("y_cg_instruments").expect("missing y_cg_instruments"),
+                *params.get("z_cg_instruments").expect("missing z_cg_instruments"),
+            ),
+        }
+    }
+
+    pub fn weight(&self) -> Weight {
+        Weight::new(
+            4.509 *
+            self.k_r *
+            self.n_c.powf(0.541) *
+            self.n_en *
+            f64::powf(self.l_f + self.b_w, 0.5)
+        )
+    }
+
+    pub fn cg(&self) -> Point<f64> {
+        self.cg
+    }
+}
+
+//------------------------------------------ avionics
+
+struct Avionics {
+    w_uav: f64,
+    cg: Point<f64>,
+}
+
+impl Avionics {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            w_uav: *params.get("w_uav").expect("missing w_uav"),
+            cg: Point::new(
+                *params.get("x_cg_avionics").expect("missing w_uav"),
+                *params.get("y_cg_avionics").expect("missing "),
+                *params.get("z_cg_avionics").expect("missing "),
+            ),
+        }
+    }
+
+    pub fn weight(&self) -> Weight {
+        Weight::new(1.73 * self.w_uav.powf(0.983))
+    }
+
+    pub fn cg(&self) -> Point<f64> {
+        self.cg
+    }
+}
+
+//------------------------------------------ flight controls
+
+struct FlightControls {
+    n_f: f64,
+    s_cs: f64,
+    i_y: f64, // yawing moment of inertia (lb.ft2), initial guess until computed
+    n_m: f64,
+    // balance
+    cg: Point<f64>,
+}
+
+impl FlightControls {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            n_f: *params.get("n_f").expect("missing n_f"),
+            s_cs: *params.get("s_cs").expect("missing s_cs"),
+            i_y: *params.get("i_y").expect("missing i_y"),
+            n_m: *params.get("n_m").expect("missing n_m"),
+            cg: Point::new(
+                *params.get("x_cg_flight_controls").expect("missing x_cg_flight_controls"),
+                *params.get("y_cg_flight_controls").expect("missing y_cg_flight_controls"),
+                *params.get("z_cg_flight_controls").expect("missing z_cg_flight_controls"),
+            ),
+        }
+    }
+
+    pub fn weight(&self) -> Weight {
+        Weight::new(
+            // numerator
+            145.9 *
+            self.n_f.powf(0.554) *
+            self.s_cs.powf(0.2) *
+            f64::powf(self.i_y * 1e-6, 0.07) /
+            // denominator
+            (1. + self.n_m / self.n_f)
+        )
+    }
+
+    pub fn cg(&self) -> Point<f64> {
+        self.cg
+    }
+}
+
+//------------------------------------------ installed apu
+
+struct InstalledApu {
+    w_apu: f64,
+    cg: Point<f64>,
+}
+
+impl InstalledApu {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            w_apu: *params.get("w_apu").expect("missing w_apu"),
+            cg: Point::new(
+                *params.get("x_cg_installed_apu").expect("missing x_cg_installed_apu"),
+                *params.get("y_cg_installed_apu").expect("missing y_cg_installed_apu"),
+                *params.get("z_cg_installed_apu").expect("missing z_cg_installed_apu"),
+            ),
+        }
+    }
+
+    pub fn weight(&self) -> Weight {
+        Weight::new(2.2 * self.w_apu)
+    }
+
+    pub fn cg(&self) -> Point<f64> {
+        self.cg
+    }
+}
+
+//------------------------------------------ anti icing
+
+struct AntiIcing {
+    cg: Point<f64>,
+}
+
+impl AntiIcing {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            cg: Point::new(
+                *params.get("x_cg_anti_icing").expect("missing x_cg_anti_icing"),
+                *params.get("y_cg_anti_icing").expect("missing y_cg_anti_icing"),
+                *params.get("z_cg_anti_icing").expect("missing z_cg_anti_icing"),
+            ),
+        }
+    }
+
+    /// - w_dg: design gross weight in lb
+    pub fn weight(&self, w_dg: f64) -> Weight {
+        Weight::new(0.002 * w_dg)
+    }
+
+    pub fn cg(&self) -> Point<f64> {
+        self.cg
+    }
+}
+
+//------------------------------------------ handling gear
+
+struct HandlingGear {
+    cg: Point<f64>,
+}
+
+impl HandlingGear {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            cg: Point::new(
+                *params.get("x_cg_handling_gear").expect("missing x_cg_handling_gear"),
+                *params.get("y_cg_handling_gear").expect("missing y_cg_handling_gear"),
+                *params.get("z_cg_handling_gear").expect("missing z_cg_handling_gear"),
+            ),
+        }
+    }
+
+    /// - w_dg: design gross weight in lb
+    pub fn weight(&self, w_dg: f64) -> Weight {
+        Weight::new(0.0003 * w_dg)
+    }
+
+    pub fn cg(&self) -> Point<f64> {
+        self.cg
+    }
+}
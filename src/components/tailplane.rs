@@ -1,6 +1,10 @@
 use crate::utils::point::Point;
 use crate::Params;
 use crate::utils::weight::Weight;
+use crate::components::Item;
+use crate::components::method::Method;
+use crate::components::technology::{Technology, Structure};
+use crate::aerodynamics::planform::Planform;
 
 pub struct Tailplane {
     ht: HorizontalTailplane,
@@ -26,6 +30,37 @@ impl Tailplane {
         self.ht.cg() * self.ht.weight(w_dg).get_val() +
         self.vt.cg() * self.vt.weight(w_dg).get_val()
     }
+
+    pub fn items(&self, w_dg: f64) -> Vec<Item> {
+        vec![
+            Item::distributed(
+                "horizontal tailplane",
+                self.ht.weight(w_dg),
+                self.ht.cg(),
+                Point::new(0., self.ht.b_ht, 0.),
+            ).with_method(self.ht.method)
+            .with_technology(self.ht.technology.factor()),
+            Item::distributed(
+                "vertical tailplane",
+                self.vt.weight(w_dg),
+                self.vt.cg(),
+                Point::new(0., 0., self.vt.fin_height_vt),
+            ).with_method(self.vt.method)
+            .with_technology(self.vt.technology.factor()),
+        ]
+    }
+
+    pub fn set_method(&mut self, method: Method) {
+        self.ht.method = method;
+        self.vt.method = method;
+    }
+
+    /// - k_y: aircraft pitching radius of gyration (ft)
+    /// - k_z: aircraft yaw radius of gyration (ft)
+    pub fn set_radii_of_gyration(&mut self, k_y: f64, k_z: f64) {
+        self.ht.k_y = k_y;
+        self.vt.k_z = k_z;
+    }
 }
 
 struct HorizontalTailplane {
@@ -44,54 +79,109 @@ struct HorizontalTailplane {
     root_ht: Point<f64>, // position of the root of the horizontal tailplane
     chord_ht: f64, // leading edge to trailing edge distance in ft
     aoa_ht: f64, // horizontal tailplane angle of attack
+    // alternative methods
+    method: Method,
+    technology: Technology,
+    c_bar: f64, // wing mean aerodynamic chord (ft)
+    t_c_ratio_root_h: Option<f64>, // Horizontal tailplane root thickness to chord ratio
+    v_d: Option<f64>, // design dive speed (keas)
+    k_h: Option<f64>, // 1.1 for variable incidence tailplane, 1.0 otherwise
 }
 
 impl HorizontalTailplane {
     pub fn new(params: &Params) -> Self {
         Self {
-            n_z: params.get("n_z").expect("missing n_z").clone(),
-            k_uht: params.get("k_uht").expect("missing k_uht").clone(),
-            s_ht: params.get("s_ht").expect("missing s_ht").clone(),
-            k_y: params.get("k_y").expect("missing k_y").clone(),
-            ar_h: params.get("ar_h").expect("missing ar_h").clone(),
-            s_e: params.get("s_e").expect("missing s_e").clone(),
-            f_w: params.get("f_w").expect("missing f_w").clone(),
-            b_ht: params.get("b_ht").expect("missing b_ht").clone(),
-            l_ht: params.get("l_ht").expect("missing l_ht").clone(),
-            sweep_ht: params.get("sweep_ht").expect("missing sweep_ht").clone(),
+            n_z: *params.get("n_z").expect("missing n_z"),
+            k_uht: *params.get("k_uht").expect("missing k_uht"),
+            s_ht: *params.get("s_ht").expect("missing s_ht"),
+            k_y: *params.get("k_y").expect("missing k_y"),
+            ar_h: *params.get("ar_h").expect("missing ar_h"),
+            s_e: *params.get("s_e").expect("missing s_e"),
+            f_w: *params.get("f_w").expect("missing f_w"),
+            b_ht: *params.get("b_ht").expect("missing b_ht"),
+            l_ht: *params.get("l_ht").expect("missing l_ht"),
+            sweep_ht: *params.get("sweep_ht").expect("missing sweep_ht"),
             root_ht: Point::new(
-                params.get("x_root_ht").expect("missing x_root_ht").clone(),
-                params.get("y_root_ht").expect("missing y_root_ht").clone(),
-                params.get("z_root_ht").expect("missing z_root_ht").clone(),
+                *params.get("x_root_ht").expect("missing x_root_ht"),
+                *params.get("y_root_ht").expect("missing y_root_ht"),
+                *params.get("z_root_ht").expect("missing z_root_ht"),
             ),
-            chord_ht: params.get("chord_ht").expect("missing chord_ht").clone(),
-            aoa_ht: params.get("aoa_ht").expect("missing aoa_ht").clone(),
+            chord_ht: *params.get("chord_ht").expect("missing chord_ht"),
+            aoa_ht: *params.get("aoa_ht").expect("missing aoa_ht"),
+            // alternative methods, Raymer unless chosen
+            method: Method::from_code(params.get("method_horizontal_tailplane").copied().unwrap_or(0.))
+                .expect("invalid method_horizontal_tailplane"),
+            technology: Technology::new(params, "horizontal_tailplane", Structure::Tail),
+            c_bar: params.get("c_bar").copied().unwrap_or_else(|| Planform::wing(params).mac()),
+            t_c_ratio_root_h: params.get("t_c_ratio_root_h").copied(),
+            v_d: params.get("v_d").copied(),
+            k_h: params.get("k_h").copied(),
         }
     }
 
     /// - w_dg: design gross weight in lb
     /// - n_z: ultimate load factor, 1.5x limit load factor
     pub fn weight(&self, w_dg: f64) -> Weight {
-        Weight::new(
-            // numerator
-            0.0379 *
-            self.k_uht *
-            f64::powf(w_dg, 0.639) *
-            f64::powf(self.n_z, 0.1) *
-            f64::powf(self.s_ht, 0.75) *
-            f64::powf(self.k_y, 0.704) *
-            f64::powf(self.ar_h, 0.166) *
-            f64::powf(1.0 + (self.s_e) / self.s_ht, 0.1) /
-            // denominator
-            f64::powf(1. + self.f_w / self.b_ht, 0.25) /
-            self.l_ht /
-            f64::cos(self.sweep_ht)
+        Weight::new(self.technology.factor() * self.method.estimate(
+            || self.raymer(w_dg),
+            || self.torenbeek(),
+            || self.general_dynamics(w_dg),
+            || self.usaf(w_dg),
+        ))
+    }
+
+    fn raymer(&self, w_dg: f64) -> f64 {
+        // numerator
+        0.0379 *
+        self.k_uht *
+        f64::powf(w_dg, 0.639) *
+        f64::powf(self.n_z, 0.1) *
+        f64::powf(self.s_ht, 0.75) *
+        f64::powf(self.k_y, 0.704) *
+        f64::powf(self.ar_h, 0.166) *
+        f64::powf(1.0 + (self.s_e) / self.s_ht, 0.1) /
+        // denominator
+        f64::powf(1. + self.f_w / self.b_ht, 0.25) /
+        self.l_ht /
+        f64::cos(self.sweep_ht)
+    }
+
+    fn torenbeek(&self) -> f64 {
+        self.k_h.expect("missing k_h") *
+        self.s_ht *
+        (
+            3.81 * self.s_ht.powf(0.2) * self.v_d.expect("missing v_d") /
+            (1000. * self.sweep_ht.cos().sqrt()) -
+            0.287
+        )
+    }
+
+    fn general_dynamics(&self, w_dg: f64) -> f64 {
+        let t_rh = self.t_c_ratio_root_h.expect("missing t_c_ratio_root_h") * self.chord_ht;
+        0.0034 * f64::powf(
+            f64::powf(w_dg * self.n_z, 0.813) *
+            self.s_ht.powf(0.584) *
+            f64::powf(self.b_ht / t_rh, 0.033) *
+            f64::powf(self.c_bar / self.l_ht, 0.28),
+            0.915,
+        )
+    }
+
+    fn usaf(&self, w_dg: f64) -> f64 {
+        let t_rh = self.t_c_ratio_root_h.expect("missing t_c_ratio_root_h") * self.chord_ht;
+        127. * f64::powf(
+            f64::powf(w_dg * self.n_z * 1e-5, 0.87) *
+            f64::powf(self.s_ht / 100., 1.2) *
+            0.289 *
+            f64::powf(self.l_ht / 10., 0.483) *
+            f64::powf(self.b_ht / t_rh, 0.5),
+            0.458,
         )
     }
 
     pub fn cg(&self) -> Point<f64> {
-        let x = self.root_ht.x() * 0.42;
-        let cg = Point::new(x, 0., self.root_ht.z() - x * self.aoa_ht.tan());
+        let x = self.chord_ht * 0.42;
+        let cg = Point::new(x, 0., -x * self.aoa_ht.tan());
         Point::new(self.root_ht.x() + cg.x(), self.root_ht.y() + cg.y(), self.root_ht.z() + cg.z())
     }
 }
@@ -111,48 +201,110 @@ struct VerticalTailplane {
     root_vt: Point<f64>, // point where the vertival tailplane is attached to the fuselage
     chord_55_vt: f64, // chord length at 55% of the fin height from the root chord
     fin_height_vt: f64, // fin height in ft
+    // alternative methods
+    method: Method,
+    technology: Technology,
+    s_ht: f64, // horizontal tailplane area in ft^2
+    m_h: Option<f64>, // maximum mach number at sea level
+    s_r: Option<f64>, // Rudder area (ft2)
+    lambda_v: Option<f64>, // Vertical tailplane taper ratio
+    v_d: Option<f64>, // design dive speed (keas)
 }
 
 impl VerticalTailplane{
     pub fn new(params: &Params) -> Self {
         Self {
             // weight
-            n_z: params.get("n_z").expect("missing n_z").clone(),
-            h_t: params.get("h_t").expect("missing h_t").clone(),
-            h_v: params.get("h_v").expect("missing h_v").clone(),
-            s_vt: params.get("s_vt").expect("missing s_vt").clone(),
-            k_z: params.get("k_z").expect("missing k_z").clone(),
-            ar_v: params.get("ar_v").expect("missing ar_v").clone(),
-            l_vt: params.get("l_vt").expect("missing l_vt").clone(),
-            sweep_vt: params.get("sweep_vt").expect("missing sweep_vt").clone(),
-            t_c_ratio_root_v: params.get("t_c_ratio_root_v").expect("missing t_c_ratio_root_v").clone(),
+            n_z: *params.get("n_z").expect("missing n_z"),
+            h_t: *params.get("h_t").expect("missing h_t"),
+            h_v: *params.get("h_v").expect("missing h_v"),
+            s_vt: *params.get("s_vt").expect("missing s_vt"),
+            k_z: *params.get("k_z").expect("missing k_z"),
+            ar_v: *params.get("ar_v").expect("missing ar_v"),
+            l_vt: *params.get("l_vt").expect("missing l_vt"),
+            sweep_vt: *params.get("sweep_vt").expect("missing sweep_vt"),
+            t_c_ratio_root_v: *params.get("t_c_ratio_root_v").expect("missing t_c_ratio_root_v"),
             // balance
             root_vt: Point::new(
-                params.get("x_root_vt").expect("missing x_root_vt").clone(),
-                params.get("y_root_vt").expect("missing y_root_vt").clone(),
-                params.get("z_root_vt").expect("missing z_root_vt").clone(),
+                *params.get("x_root_vt").expect("missing x_root_vt"),
+                *params.get("y_root_vt").expect("missing y_root_vt"),
+                *params.get("z_root_vt").expect("missing z_root_vt"),
             ),
-            chord_55_vt: params.get("chord_55_vt").expect("missing chord_55_vt").clone(),
-            fin_height_vt: params.get("fin_height_vt").expect("missing fin_height_vt").clone(),
+            chord_55_vt: *params.get("chord_55_vt").expect("missing chord_55_vt"),
+            fin_height_vt: *params.get("fin_height_vt").expect("missing fin_height_vt"),
+            // alternative methods, Raymer unless chosen
+            method: Method::from_code(params.get("method_vertical_tailplane").copied().unwrap_or(0.))
+                .expect("invalid method_vertical_tailplane"),
+            technology: Technology::new(params, "vertical_tailplane", Structure::Tail),
+            s_ht: *params.get("s_ht").expect("missing s_ht"),
+            m_h: params.get("m_h").copied(),
+            s_r: params.get("s_r").copied(),
+            lambda_v: params.get("lambda_v").copied(),
+            v_d: params.get("v_d").copied(),
         }
     }
 
     /// - w_dg: design gross weight in lb
     /// - n_z: ultimate load factor, 1.5x limit load factor
     pub fn weight(&self, w_dg: f64) -> Weight {
-        Weight::new(
-            // numerator
-            0.0026 *
-            f64::powf(1. + self.h_t / self.h_v, 0.225) *
-            w_dg.powf(0.556) *
-            self.n_z.powf(0.536) *
-            self.s_vt.powf(0.5) *
-            self.k_z.powf(0.875) *
-            self.ar_v.powf(0.35) /
-            // denominator
-            self.l_vt.powf(0.5) /
-            self.sweep_vt.cos() /
-            self.t_c_ratio_root_v.powf(0.5)
+        Weight::new(self.technology.factor() * self.method.estimate(
+            || self.raymer(w_dg),
+            || self.torenbeek(),
+            || self.general_dynamics(w_dg),
+            || self.usaf(w_dg),
+        ))
+    }
+
+    fn raymer(&self, w_dg: f64) -> f64 {
+        // numerator
+        0.0026 *
+        f64::powf(1. + self.h_t / self.h_v, 0.225) *
+        w_dg.powf(0.556) *
+        self.n_z.powf(0.536) *
+        self.s_vt.powf(0.5) *
+        self.k_z.powf(0.875) *
+        self.ar_v.powf(0.35) /
+        // denominator
+        self.l_vt.powf(0.5) /
+        self.sweep_vt.cos() /
+        self.t_c_ratio_root_v.powf(0.5)
+    }
+
+    fn torenbeek(&self) -> f64 {
+        let k_v = 1. + 0.15 * self.s_ht * self.h_t / (self.s_vt * self.h_v);
+        k_v *
+        self.s_vt *
+        (
+            3.81 * self.s_vt.powf(0.2) * self.v_d.expect("missing v_d") /
+            (1000. * self.sweep_vt.cos().sqrt()) -
+            0.287
+        )
+    }
+
+    fn general_dynamics(&self, w_dg: f64) -> f64 {
+        0.19 * f64::powf(
+            f64::powf(1. + self.h_t / self.h_v, 0.5) *
+            f64::powf(w_dg * self.n_z, 0.363) *
+            self.s_vt.powf(1.089) *
+            self.m_h.expect("missing m_h").powf(0.601) *
+            self.l_vt.powf(-0.726) *
+            f64::powf(1. + self.s_r.expect("missing s_r") / self.s_vt, 0.217) *
+            self.ar_v.powf(0.337) *
+            f64::powf(1. + self.lambda_v.expect("missing lambda_v"), 0.363) *
+            self.sweep_vt.cos().powf(-0.484),
+            1.014,
+        )
+    }
+
+    fn usaf(&self, w_dg: f64) -> f64 {
+        let root_chord = 2. * self.s_vt / (self.h_v * (1. + self.lambda_v.expect("missing lambda_v")));
+        let t_rv = self.t_c_ratio_root_v * root_chord;
+        98.5 * f64::powf(
+            f64::powf(w_dg * self.n_z * 1e-5, 0.87) *
+            f64::powf(self.s_vt / 100., 1.2) *
+            0.289 *
+            f64::powf(self.h_v / t_rv, 0.5),
+            0.458,
         )
     }
 
@@ -0,0 +1,81 @@
+use crate::Params;
+
+/// Kind of structure, which sets the composite correction.
+#[derive(Copy, Clone, PartialEq)]
+pub enum Structure {
+    Wing,
+    Tail,
+    Fuselage,
+    Nacelle,
+    LandingGear,
+}
+
+impl Structure {
+    /// Middle of Raymer's advanced composites fudge factors (table 15.4).
+    fn composite_factor(&self) -> f64 {
+        match self {
+            Structure::Wing => 0.875,
+            Structure::Tail => 0.855,
+            Structure::Fuselage | Structure::Nacelle => 0.925,
+            Structure::LandingGear => 0.975,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum Material {
+    Aluminium,
+    Composite,
+}
+
+impl Material {
+    /// 0 for aluminium, 1 for composite
+    pub fn from_code(code: f64) -> Result<Self, String> {
+        let material = match code as i32 {
+            0 => Material::Aluminium,
+            1 => Material::Composite,
+            _ => return Err(format!("unknown material {}, expected 0 or 1", code)),
+        };
+        Ok(material)
+    }
+}
+
+/// Correction applied on top of the weight estimation method of a component.
+/// The parameters are suffixed by the component name, e.g.
+/// material_wings_structure, and the year of entry into service falls back to
+/// year_eis for the whole aircraft. A year of entry into service needs the
+/// year_reference of the aircraft behind the statistical equations and the
+/// weight reduction per year of progress, k_year, which have no default.
+pub struct Technology {
+    structure: Structure,
+    material: Material,
+    k_manufacturing: f64, // advanced manufacturing factor, 1.0 for conventional
+    progress: Option<(f64, f64)>, // years since the reference year, weight reduction per year
+}
+
+impl Technology {
+    pub fn new(params: &Params, component: &str, structure: Structure) -> Self {
+        let get = |name: &str| params.get(&format!("{}_{}", name, component)).copied();
+        Self {
+            structure,
+            material: Material::from_code(get("material").unwrap_or(0.)).expect("invalid material"),
+            k_manufacturing: get("k_manufacturing").unwrap_or(1.),
+            progress: get("year_eis").or_else(|| params.get("year_eis").copied()).map(|year_eis| (
+                year_eis - *params.get("year_reference").expect("missing year_reference"),
+                *params.get("k_year").expect("missing k_year"),
+            )),
+        }
+    }
+
+    pub fn factor(&self) -> f64 {
+        let material = match self.material {
+            Material::Aluminium => 1.,
+            Material::Composite => self.structure.composite_factor(),
+        };
+        let year = match self.progress {
+            Some((years, k_year)) => f64::powf(1. - k_year, years),
+            None => 1.,
+        };
+        material * self.k_manufacturing * year
+    }
+}
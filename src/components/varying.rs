@@ -1,6 +1,8 @@
 use crate::utils::weight::Weight;
 use crate::utils::point::Point;
 use crate::utils::in_to_ft;
+use crate::components::Item;
+use crate::evaluation::{NUM_ROWS, PASSENGERS_ABREAST};
 
 pub struct Pilots {
     num_pilots: f64, // number of pilots
@@ -24,6 +26,10 @@ impl Pilots {
     pub fn pos_times_weight(&self) -> Point<f64> {
         self.cg * self.weight().get_val()
     }
+
+    pub fn item(&self) -> Item {
+        Item::point("pilots", self.weight(), self.cg)
+    }
 }
 
 pub struct Crew {
@@ -48,12 +54,18 @@ impl Crew {
     pub fn pos_times_weight(&self) -> Point<f64> {
         self.cg * self.weight().get_val()
     }
+
+    pub fn item(&self) -> Item {
+        Item::point("crew", self.weight(), self.cg)
+    }
 }
 
+#[derive(Copy, Clone)]
 pub enum LoadCase {
     Front,
     Rear,
     Center,
+    Rows { first: f64, last: f64 }, // seated from row first to row last, from 1 at the front
 }
 
 pub struct Passengers {
@@ -77,13 +89,21 @@ impl Passengers {
 
     pub fn pos_times_weight(&self) -> Point<f64> {
         let start_of_seats = Point::new(in_to_ft(39.37 + 6. + in_to_ft(36. + 17.)), 0., 0.);
-        let length_seats = 15. * in_to_ft(32.) + in_to_ft(18.);
-        let length_row = length_seats / 15.;
-        match self.load_case {
-            LoadCase::Center => (start_of_seats + length_seats * 0.5) * self.weight().get_val(),
-            LoadCase::Front => (start_of_seats + self.num_passengers / 6. * length_row) * self.weight().get_val(),
-            LoadCase::Rear => (start_of_seats + length_seats - self.num_passengers / 6. * length_row) * self.weight().get_val(),
-        }
+        let length_seats = NUM_ROWS * in_to_ft(32.) + in_to_ft(18.);
+        let length_row = length_seats / NUM_ROWS;
+        // middle of the occupied rows, on the centerline
+        let length_occupied = self.num_passengers / PASSENGERS_ABREAST * length_row;
+        let x = match self.load_case {
+            LoadCase::Center => length_seats * 0.5,
+            LoadCase::Front => length_occupied * 0.5,
+            LoadCase::Rear => length_seats - length_occupied * 0.5,
+            LoadCase::Rows { first, last } => (first - 1. + last) * 0.5 * length_row,
+        };
+        (start_of_seats + Point::new(x, 0., 0.)) * self.weight().get_val()
+    }
+
+    pub fn item(&self) -> Item {
+        Item::point("passengers", self.weight(), self.pos_times_weight() / self.weight().get_val())
     }
 }
 
@@ -93,10 +113,10 @@ pub struct Payload {
 }
 
 impl Payload {
-    pub fn new(payload_weight: f64) -> Self {
+    pub fn new(payload_weight: f64, cg: Point<f64>) -> Self {
         Self {
             payload_weight,
-            cg: Point::new(0., 0., 0.),
+            cg,
         }
     }
 
@@ -107,6 +127,10 @@ impl Payload {
     pub fn pos_times_weight(&self) -> Point<f64> {
         self.cg * self.weight().get_val()
     }
+
+    pub fn item(&self) -> Item {
+        Item::point("payload", self.weight(), self.cg)
+    }
 }
 
 pub struct Fuel {
@@ -129,4 +153,20 @@ impl Fuel {
     pub fn pos_times_weight(&self) -> Point<f64> {
         self.cg * self.weight().get_val()
     }
+
+    pub fn item(&self) -> Item {
+        Item::point("fuel", self.weight(), self.cg)
+    }
+
+    /// Splits the fuel between the left and right wing tanks.
+    /// - y_tank: lateral position of each tank (ft)
+    /// - imbalance: right tank minus left tank fuel weight (lb)
+    pub fn wing_tanks(&self, y_tank: f64, imbalance: f64) -> [Item; 2] {
+        let left = Weight::new(0.5 * (self.fuel_weight - imbalance));
+        let right = Weight::new(0.5 * (self.fuel_weight + imbalance));
+        [
+            Item::point("fuel left", left, Point::new(*self.cg.x(), -y_tank, *self.cg.z())),
+            Item::point("fuel right", right, Point::new(*self.cg.x(), y_tank, *self.cg.z())),
+        ]
+    }
 }
@@ -1,13 +1,17 @@
 use crate::utils::point::Point;
 use crate::utils::weight::Weight;
 use crate::Params;
+use crate::components::Item;
+use crate::components::engines::Engines;
+use crate::components::method::Method;
+use crate::components::technology::{Technology, Structure};
+use crate::aerodynamics::sweep_at;
 
 pub struct Wings {
     wings_structure: WingsStructure,
     nacelle: Nacelle,
     engine_controls: EngineControls,
     fuel_system: FuelSystem,
-    engine_pneumatic_starter: EnginePneumaticStarter,
 }
 
 impl Wings {
@@ -17,7 +21,6 @@ impl Wings {
             nacelle: Nacelle::new(params),
             engine_controls: EngineControls::new(params),
             fuel_system: FuelSystem::new(params),
-            engine_pneumatic_starter: EnginePneumaticStarter::new(params),
         }
     }
 
@@ -25,8 +28,7 @@ impl Wings {
         self.wings_structure.weight(w_dg) +
         self.nacelle.weight() +
         self.engine_controls.weight() +
-        self.fuel_system.weight() +
-        self.engine_pneumatic_starter.weight()
+        self.fuel_system.weight()
     }
 
     /// wings_root_pos: position of the root of the wing
@@ -34,8 +36,47 @@ impl Wings {
         (wings_root_pos + self.wings_structure.cg()) * self.wings_structure.weight(w_dg).get_val() +
         (wings_root_pos + self.nacelle.cg()) * self.nacelle.weight().get_val() +
         (wings_root_pos + self.engine_controls.cg()) * self.engine_controls.weight().get_val() +
-        (wings_root_pos + self.fuel_system.cg()) * self.fuel_system.weight().get_val() +
-        (wings_root_pos + self.engine_pneumatic_starter.cg()) * self.engine_pneumatic_starter.weight().get_val()
+        (wings_root_pos + self.fuel_system.cg()) * self.fuel_system.weight().get_val()
+    }
+
+    /// wings_root_pos: position of the root of the wing
+    pub fn items(&self, w_dg: f64, wings_root_pos: Point<f64>) -> Vec<Item> {
+        vec![
+            Item::distributed(
+                "wings structure",
+                self.wings_structure.weight(w_dg),
+                wings_root_pos + self.wings_structure.cg(),
+                Point::new(0., self.wings_structure.wing_span, 0.),
+            ).with_method(self.wings_structure.method)
+            .with_technology(self.wings_structure.technology.factor()),
+            // one nacelle on each side
+            Item::point(
+                "nacelle left",
+                Weight::new(0.5 * self.nacelle.weight().get_val()),
+                wings_root_pos + self.nacelle.side_cg(-1.),
+            ).with_method(Method::Raymer)
+            .with_technology(self.nacelle.technology.factor()),
+            Item::point(
+                "nacelle right",
+                Weight::new(0.5 * self.nacelle.weight().get_val()),
+                wings_root_pos + self.nacelle.side_cg(1.),
+            ).with_method(Method::Raymer)
+            .with_technology(self.nacelle.technology.factor()),
+            Item::point(
+                "engine controls",
+                self.engine_controls.weight(),
+                wings_root_pos + self.engine_controls.cg(),
+            ).with_method(Method::Raymer),
+            Item::point(
+                "fuel system",
+                self.fuel_system.weight(),
+                wings_root_pos + self.fuel_system.cg(),
+            ).with_method(Method::Raymer),
+        ]
+    }
+
+    pub fn set_method(&mut self, method: Method) {
+        self.wings_structure.method = method;
     }
 }
 
@@ -57,42 +98,99 @@ struct WingsStructure {
     sweep_as: f64,    // aft spar sweep in radians
     dihedral: f64,    // wing quarter chord dihedral in radians
     wing_span: f64,   // wing span in ft
+    // alternative methods
+    method: Method,
+    technology: Technology,
+    m_h: Option<f64>,   // maximum mach number at sea level
+    w_mzf: Option<f64>, // maximum zero fuel weight in lb
+    v_h: Option<f64>,   // maximum level speed at sea level in kts
 }
 
 impl WingsStructure {
     pub fn new(params: &Params) -> Self {
         Self {
             // weights
-            n_z: params.get("n_z").expect("missing n_z").clone(),
-            s_w: params.get("s_w").expect("missing s_w").clone(),
-            ar: params.get("ar").expect("missing ar").clone(),
-            lambda: params.get("lambda").expect("missing lambda").clone(),
-            s_csw: params.get("s_csw").expect("missing s_csw").clone(),
-            t_c_ratio_root: params.get("t_c_ratio_root").expect("missing t_c_ratio_root").clone(),
-            sweep: params.get("sweep").expect("missing sweep").clone(),
+            n_z: *params.get("n_z").expect("missing n_z"),
+            s_w: *params.get("s_w").expect("missing s_w"),
+            ar: *params.get("ar").expect("missing ar"),
+            lambda: *params.get("lambda").expect("missing lambda"),
+            s_csw: *params.get("s_csw").expect("missing s_csw"),
+            t_c_ratio_root: *params.get("t_c_ratio_root").expect("missing t_c_ratio_root"),
+            sweep: *params.get("sweep").expect("missing sweep"),
             // balance
-            delta_fs_as: params.get("delta_fs_as").expect("missing delta_fs_as").clone(),
-            x_fs: params.get("x_fs").expect("missing x_fs").clone(),
-            sweep_fs: params.get("sweep_fs").expect("missing sweep_fs").clone(),
-            sweep_as: params.get("sweep_as").expect("missing sweep_as").clone(),
-            dihedral: params.get("dihedral").expect("missing dihedral").clone(),
-            wing_span: params.get("wing_span").expect("missing wing_span").clone(),
+            delta_fs_as: *params.get("delta_fs_as").expect("missing delta_fs_as"),
+            x_fs: *params.get("x_fs").expect("missing x_fs"),
+            sweep_fs: *params.get("sweep_fs").expect("missing sweep_fs"),
+            sweep_as: *params.get("sweep_as").expect("missing sweep_as"),
+            dihedral: *params.get("dihedral").expect("missing dihedral"),
+            wing_span: *params.get("wing_span").expect("missing wing_span"),
+            // alternative methods, Raymer unless chosen
+            method: Method::from_code(params.get("method_wings_structure").copied().unwrap_or(0.))
+                .expect("invalid method_wings_structure"),
+            technology: Technology::new(params, "wings_structure", Structure::Wing),
+            m_h: params.get("m_h").copied(),
+            w_mzf: params.get("w_mzf").copied(),
+            v_h: params.get("v_h").copied(),
         }
     }
 
     /// - w_dg: design gross weight in lb
     pub fn weight(&self, w_dg: f64) -> Weight {
-        Weight::new(
-            // numerator
-            0.0051 *
-            f64::powf(w_dg * self.n_z, 0.557) *
-            self.s_w.powf(0.649) *
-            self.ar.powf(0.5) *
-            f64::powf(1.0 + self.lambda, 0.1) *
-            self.s_csw.powf(0.1) /
-            // denominator
-            self.sweep.cos() /
-            self.t_c_ratio_root.powf(0.4)
+        Weight::new(self.technology.factor() * self.method.estimate(
+            || self.raymer(w_dg),
+            || self.torenbeek(),
+            || self.general_dynamics(w_dg),
+            || self.usaf(w_dg),
+        ))
+    }
+
+    fn raymer(&self, w_dg: f64) -> f64 {
+        // numerator
+        0.0051 *
+        f64::powf(w_dg * self.n_z, 0.557) *
+        self.s_w.powf(0.649) *
+        self.ar.powf(0.5) *
+        f64::powf(1.0 + self.lambda, 0.1) *
+        self.s_csw.powf(0.1) /
+        // denominator
+        self.sweep.cos() /
+        self.t_c_ratio_root.powf(0.4)
+    }
+
+    fn torenbeek(&self) -> f64 {
+        let w_mzf = self.w_mzf.expect("missing w_mzf");
+        let cos_sweep_half = sweep_at(0.5, self.sweep, self.ar, self.lambda).cos();
+        let t_r = self.t_c_ratio_root * 2. * self.s_w / (self.wing_span * (1. + self.lambda));
+        0.0017 *
+        w_mzf *
+        f64::powf(self.wing_span / cos_sweep_half, 0.75) *
+        (1. + f64::sqrt(6.3 * cos_sweep_half / self.wing_span)) *
+        self.n_z.powf(0.55) *
+        f64::powf(self.wing_span * self.s_w / (t_r * w_mzf * cos_sweep_half), 0.3)
+    }
+
+    fn general_dynamics(&self, w_dg: f64) -> f64 {
+        let cos_sweep_half = sweep_at(0.5, self.sweep, self.ar, self.lambda).cos();
+        // numerator
+        0.00428 *
+        self.s_w.powf(0.48) *
+        self.ar *
+        self.m_h.expect("missing m_h").powf(0.43) *
+        f64::powf(w_dg * self.n_z, 0.84) *
+        self.lambda.powf(0.14) /
+        // denominator
+        f64::powf(100. * self.t_c_ratio_root, 0.76) /
+        cos_sweep_half.powf(1.54)
+    }
+
+    fn usaf(&self, w_dg: f64) -> f64 {
+        96.948 * f64::powf(
+            f64::powf(w_dg * self.n_z * 1e-5, 0.65) *
+            f64::powf(self.ar / self.sweep.cos().powi(2), 0.57) *
+            f64::powf(self.s_w / 100., 0.61) *
+            f64::powf((1. + self.lambda) / (2. * self.t_c_ratio_root), 0.36) *
+            f64::powf(1. + self.v_h.expect("missing v_h") / 500., 0.5),
+            0.993,
         )
     }
 
@@ -120,29 +218,33 @@ struct Nacelle {
     w_enc: f64, // Weight of engine and contents in lb (see pdf)
     n_en: f64, // Number of engines
     s_n: f64, // Nacelle wetted area (ft^2)
-    cg: Point<f64>,
+    cg: Point<f64>, // CG of the right nacelle, the left one is symmetric
+    technology: Technology,
 }
 
 impl Nacelle {
     pub fn new(params: &Params) -> Self {
         Self {
-            n_z: params.get("n_z").expect("missing n_z").clone(),
-            k_ng: params.get("k_ng").expect("missing k_ng").clone(),
-            n_lt: params.get("n_lt").expect("missing n_lt").clone(),
-            n_w: params.get("n_w").expect("missing n_w").clone(),
-            w_enc: params.get("w_enc").expect("missing w_enc").clone(),
-            n_en: params.get("n_en").expect("missing n_en").clone(),
-            s_n: params.get("s_n").expect("missing s_n").clone(),
+            n_z: *params.get("n_z").expect("missing n_z"),
+            k_ng: *params.get("k_ng").expect("missing k_ng"),
+            n_lt: *params.get("n_lt").expect("missing n_lt"),
+            n_w: *params.get("n_w").expect("missing n_w"),
+            // installed engine weight unless given
+            w_enc: params.get("w_enc").copied().unwrap_or_else(|| Engines::new(params).w_ec()),
+            n_en: *params.get("n_en").expect("missing n_en"),
+            s_n: *params.get("s_n").expect("missing s_n"),
             cg: Point::new(
-                params.get("x_cg_nacelle").expect("missing x_cg_nacelle").clone(),
-                params.get("y_cg_nacelle").expect("missing y_cg_nacelle").clone(),
-                params.get("z_cg_nacelle").expect("missing z_cg_nacelle").clone(),
+                *params.get("x_cg_nacelle").expect("missing x_cg_nacelle"),
+                *params.get("y_cg_nacelle").expect("missing y_cg_nacelle"),
+                *params.get("z_cg_nacelle").expect("missing z_cg_nacelle"),
             ),
+            technology: Technology::new(params, "nacelle", Structure::Nacelle),
         }
     }
 
     pub fn weight(&self) -> Weight {
-        Weight::new(0.6724 *
+        Weight::new(self.technology.factor() *
+            0.6724 *
             self.k_ng *
             f64::powf(self.n_lt, 0.1) *
             f64::powf(self.n_w, 0.294) *
@@ -152,8 +254,14 @@ impl Nacelle {
             f64::powf(self.s_n, 0.224))
     }
 
+    /// CG of both nacelles together
     pub fn cg(&self) -> Point<f64> {
-        self.cg
+        Point::new(*self.cg.x(), 0., *self.cg.z())
+    }
+
+    /// - side: 1 for the right nacelle, -1 for the left one
+    pub fn side_cg(&self, side: f64) -> Point<f64> {
+        Point::new(*self.cg.x(), side * self.cg.y(), *self.cg.z())
     }
 }
 
@@ -168,12 +276,12 @@ struct EngineControls {
 impl EngineControls {
     pub fn new(params: &Params) -> Self {
         Self {
-            l_ec: params.get("l_ec").expect("missing l_ec").clone(),
-            n_en: params.get("n_en").expect("missing n_en").clone(),
+            l_ec: *params.get("l_ec").expect("missing l_ec"),
+            n_en: *params.get("n_en").expect("missing n_en"),
             cg: Point::new(
-                params.get("x_cg_engine_controls").expect("missing x_cg_engine_controls").clone(),
-                params.get("y_cg_engine_controls").expect("missing y_cg_engine_controls").clone(),
-                params.get("z_cg_engine_controls").expect("missing z_cg_engine_controls").clone(),
+                *params.get("x_cg_engine_controls").expect("missing x_cg_engine_controls"),
+                *params.get("y_cg_engine_controls").expect("missing y_cg_engine_controls"),
+                *params.get("z_cg_engine_controls").expect("missing z_cg_engine_controls"),
             ),
         }
     }
@@ -200,14 +308,14 @@ struct FuelSystem {
 impl FuelSystem {
     pub fn new(params: &Params) -> Self {
         Self {
-            v_t: params.get("v_t").expect("missing v_t").clone(),
-            n_t: params.get("n_t").expect("missing n_t").clone(),
-            v_p: params.get("v_p").expect("missing v_p").clone(),
-            v_i: params.get("v_i").expect("missing v_i").clone(),
+            v_t: *params.get("v_t").expect("missing v_t"),
+            n_t: *params.get("n_t").expect("missing n_t"),
+            v_p: *params.get("v_p").expect("missing v_p"),
+            v_i: *params.get("v_i").expect("missing v_i"),
             cg: Point::new(
-                params.get("x_cg_fuel_system").expect("missing x_cg_fuel_system").clone(),
-                params.get("y_cg_fuel_system").expect("missing y_cg_fuel_system").clone(),
-                params.get("z_cg_fuel_system").expect("missing z_cg_fuel_system").clone(),
+                *params.get("x_cg_fuel_system").expect("missing x_cg_fuel_system"),
+                *params.get("y_cg_fuel_system").expect("missing y_cg_fuel_system"),
+                *params.get("z_cg_fuel_system").expect("missing z_cg_fuel_system"),
             ),
         }
     }
@@ -226,34 +334,3 @@ impl FuelSystem {
         self.cg
     }
 }
-
-//---------------------------------------------------- engine pneumatic starter
-
-struct EnginePneumaticStarter {
-    n_en: f64, // Number of engines
-    w_en: f64, // Engine weight (lb)
-    cg: Point<f64>,
-}
-
-impl EnginePneumaticStarter {
-    pub fn new(params: &Params) -> Self {
-        Self {
-            n_en: params.get("n_en").expect("missing n_en").clone(),
-            w_en: params.get("w_en").expect("missing w_en").clone(),
-            cg: Point::new(
-                params.get("x_cg_engine_pneumatic_starter").expect("missing x_cg_engine_pneumatic_starter").clone(),
-                params.get("y_cg_engine_pneumatic_starter").expect("missing y_cg_engine_pneumatic_starter").clone(),
-                params.get("z_cg_engine_pneumatic_starter").expect("missing z_cg_engine_pneumatic_starter").clone(),
-            ),
-        }
-    }
-    
-    pub fn weight(&self) -> Weight {
-        Weight::new(49.19 * f64::powf(self.n_en * self.w_en * 1e-3, 0.541))
-    }
-
-    pub fn cg(&self) -> Point<f64> {
-        self.cg
-    }
-}
-
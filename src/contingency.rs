@@ -0,0 +1,206 @@
+use std::error::Error;
+use std::fs::OpenOptions;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use csv::{Reader, WriterBuilder};
+use crate::Params;
+use crate::components::Item;
+use crate::utils::point::Point;
+use crate::utils::weight::Weight;
+
+/// Design maturity of an item weight.
+#[derive(Copy, Clone, PartialEq)]
+pub enum Maturity {
+    Estimated, // statistical equations or layout
+    Calculated, // from drawings
+    Actual, // weighed or vendor weight
+}
+
+impl Maturity {
+    pub const ALL: [Maturity; 3] = [Maturity::Estimated, Maturity::Calculated, Maturity::Actual];
+
+    /// 0 for estimated, 1 for calculated, 2 for actual
+    pub fn from_code(code: f64) -> Result<Self, String> {
+        let maturity = match code as i32 {
+            0 => Maturity::Estimated,
+            1 => Maturity::Calculated,
+            2 => Maturity::Actual,
+            _ => return Err(format!("unknown maturity {}, expected 0, 1 or 2", code)),
+        };
+        Ok(maturity)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Maturity::Estimated => "estimated",
+            Maturity::Calculated => "calculated",
+            Maturity::Actual => "actual",
+        }
+    }
+}
+
+/// ATA chapter of an empty weight item, the left and right items sharing one.
+pub fn ata_chapter(name: &str) -> Option<u32> {
+    match name.trim_end_matches(" left").trim_end_matches(" right") {
+        "air conditioning" => Some(21),
+        "electrical systems" => Some(24),
+        "furnishing" | "handling gear" => Some(25),
+        "flight controls" => Some(27),
+        "fuel system" => Some(28),
+        "hydraulic system" => Some(29),
+        "anti icing" => Some(30),
+        "instruments" => Some(31),
+        "main landing gear" | "nose landing gear" => Some(32),
+        "avionics" => Some(34),
+        "installed apu" => Some(49),
+        "fuselage structure" => Some(53),
+        "nacelle" => Some(54),
+        "horizontal tailplane" | "vertical tailplane" => Some(55),
+        "wings structure" => Some(57),
+        "propellers" => Some(61),
+        "engines" => Some(72),
+        "engine controls" => Some(76),
+        "thrust reversers" => Some(78),
+        "oil system" => Some(79),
+        "engine pneumatic starter" => Some(80),
+        _ => None,
+    }
+}
+
+/// Contingency of one item.
+pub struct ContingencyLine {
+    name: &'static str,
+    ata: Option<u32>,
+    maturity: Maturity,
+    weight: f64, // computed weight (lb)
+    contingency: f64, // lb
+    cg: Point<f64>,
+}
+
+impl ContingencyLine {
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn ata(&self) -> Option<u32> {
+        self.ata
+    }
+
+    pub fn maturity(&self) -> Maturity {
+        self.maturity
+    }
+
+    pub fn weight(&self) -> f64 {
+        self.weight
+    }
+
+    pub fn contingency(&self) -> f64 {
+        self.contingency
+    }
+}
+
+/// Weight growth allowance by design maturity. Each item is estimated, or
+/// actual when its weight is given, unless maturity_<item> or
+/// maturity_ata_<chapter> is given, and its percentage is the one of its
+/// maturity unless contingency_<item> or contingency_ata_<chapter> is given,
+/// the item settings overriding the chapter ones. Item names use
+/// underscores, without the side.
+pub struct Contingency {
+    params: Params,
+    estimated: f64, // contingency of estimated weights (fraction)
+    calculated: f64, // contingency of calculated weights (fraction)
+    actual: f64, // contingency of actual weights (fraction)
+}
+
+impl Contingency {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            params: params.iter()
+                .filter(|(symbol, _)| symbol.starts_with("maturity_") || symbol.starts_with("contingency_"))
+                .map(|(symbol, value)| (symbol.clone(), *value))
+                .collect(),
+            estimated: params.get("contingency_estimated").copied().unwrap_or(0.10),
+            calculated: params.get("contingency_calculated").copied().unwrap_or(0.05),
+            actual: params.get("contingency_actual").copied().unwrap_or(0.),
+        }
+    }
+
+    /// Item setting, or the setting of its ATA chapter.
+    fn setting(&self, prefix: &str, item: &Item) -> Option<f64> {
+        let name = item.base_name().replace(' ', "_");
+        self.params.get(&format!("{}_{}", prefix, name)).copied().or_else(|| {
+            ata_chapter(item.name())
+                .and_then(|ata| self.params.get(&format!("{}_ata_{}", prefix, ata)).copied())
+        })
+    }
+
+    pub fn line(&self, item: &Item) -> ContingencyLine {
+        let given = item.estimate().is_some() && item.calibration().is_none();
+        let maturity = Maturity::from_code(
+            self.setting("maturity", item).unwrap_or(if given { 2. } else { 0. })
+        ).expect("invalid maturity");
+        let fraction = self.setting("contingency", item).unwrap_or(match maturity {
+            Maturity::Estimated => self.estimated,
+            Maturity::Calculated => self.calculated,
+            Maturity::Actual => self.actual,
+        });
+        ContingencyLine {
+            name: item.name(),
+            ata: ata_chapter(item.name()),
+            maturity,
+            weight: item.weight().get_val(),
+            contingency: fraction * item.weight().get_val(),
+            cg: item.cg(),
+        }
+    }
+
+    pub fn lines(&self, items: &[Item]) -> Vec<ContingencyLine> {
+        items.iter().map(|item| self.line(item)).collect()
+    }
+}
+
+/// Contingency of all the lines as one item, at the CG of the allowances.
+pub fn contingency_item(lines: &[ContingencyLine]) -> Item {
+    let weight: f64 = lines.iter().map(|line| line.contingency).sum();
+    let moment = lines.iter()
+        .fold(Point::new(0., 0., 0.), |r, line| r + line.cg * line.contingency);
+    let cg = if weight > 0. { moment / weight } else { Point::new(0., 0., 0.) };
+    Item::point("contingency", Weight::new(weight), cg)
+}
+
+/// Appends the weight in each maturity state and the total contingency to
+/// the history file, to follow the burn down from one recorded design
+/// iteration to the next. A snapshot identical to the last one is not
+/// appended; returns whether it was.
+pub fn append_history(path: &str, lines: &[ContingencyLine]) -> Result<bool, Box<dyn Error>> {
+    let mut values = vec![];
+    for maturity in Maturity::ALL {
+        let weight: f64 = lines.iter()
+            .filter(|line| line.maturity == maturity)
+            .map(|line| line.weight)
+            .sum();
+        values.push(weight.to_string());
+    }
+    values.push(lines.iter().map(|line| line.contingency).sum::<f64>().to_string());
+
+    let records = if Path::new(path).exists() {
+        Reader::from_path(path)?.records().collect::<Result<Vec<_>, _>>()?
+    } else {
+        vec![]
+    };
+    if records.last().is_some_and(|last| last.iter().skip(2).eq(values.iter().map(String::as_str))) {
+        return Ok(false);
+    }
+    let run = records.len() + 1;
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    let mut writer = WriterBuilder::new().has_headers(false).from_writer(file);
+    if run == 1 {
+        writer.write_record(["run", "time", "estimated", "calculated", "actual", "contingency"])?;
+    }
+    let time = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let mut record = vec![run.to_string(), time.to_string()];
+    record.extend(values);
+    writer.write_record(&record)?;
+    writer.flush()?;
+    Ok(true)
+}
@@ -0,0 +1,79 @@
+use crate::Params;
+
+const RHO_SL: f64 = 0.002377; // sea level air density (slug/ft^3)
+
+/// One engine inoperative directional control: the rudder has to balance the
+/// yawing moment of the live outboard engine (plus the windmilling drag of the
+/// dead one) with the bank angle and sideslip taken as zero.
+pub struct EngineOut {
+    // asymmetric thrust
+    y_en: f64, // lateral position of the critical engine, from the nacelle CG (ft)
+    t_en: f64, // take-off thrust per engine (lb)
+    k_wm: f64, // windmilling drag of the failed engine as a fraction of t_en
+    // fin and rudder
+    s_vt: f64, // Vertical tailplane area (ft2)
+    l_vt: f64, // Length from wing aerodynamic centre to vertical tailplane aerodynamic centre (ft)
+    c_lav: f64, // fin lift curve slope (per rad)
+    eta_v: f64, // fin dynamic pressure ratio
+    tau_r: f64, // rudder effectiveness, fin angle of attack per unit rudder deflection
+    delta_r_max: f64, // maximum rudder deflection (rad)
+    // reference speed
+    v_s: f64, // Landing stall speed (ft/s)
+}
+
+impl EngineOut {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            // asymmetric thrust
+            y_en: params.get("y_cg_nacelle").expect("missing y_cg_nacelle").abs(),
+            t_en: *params.get("t_en").expect("missing t_en"),
+            k_wm: *params.get("k_wm").expect("missing k_wm"),
+            // fin and rudder
+            s_vt: *params.get("s_vt").expect("missing s_vt"),
+            l_vt: *params.get("l_vt").expect("missing l_vt"),
+            c_lav: *params.get("c_lav").expect("missing c_lav"),
+            eta_v: *params.get("eta_v").expect("missing eta_v"),
+            tau_r: *params.get("tau_r").expect("missing tau_r"),
+            delta_r_max: *params.get("delta_r_max").expect("missing delta_r_max"),
+            // reference speed
+            v_s: *params.get("v_s").expect("missing v_s"),
+        }
+    }
+
+    /// Yawing moment to be balanced by the rudder (lb.ft).
+    pub fn asymmetric_yawing_moment(&self) -> f64 {
+        self.t_en * (1. + self.k_wm) * self.y_en
+    }
+
+    /// Yawing moment produced by the fin at full rudder deflection (lb.ft).
+    /// - v: true airspeed at sea level (ft/s)
+    pub fn rudder_yawing_moment(&self, v: f64) -> f64 {
+        0.5 * RHO_SL * v * v * self.fin_power()
+    }
+
+    /// Minimum control speed in the air (ft/s), where the full rudder moment
+    /// equals the asymmetric thrust moment.
+    pub fn vmca(&self) -> f64 {
+        f64::sqrt(2. * self.asymmetric_yawing_moment() / (RHO_SL * self.fin_power()))
+    }
+
+    /// Highest acceptable VMCA (ft/s), 1.2 Vs.
+    pub fn vmca_limit(&self) -> f64 {
+        1.2 * self.v_s
+    }
+
+    pub fn meets_vmca_limit(&self) -> bool {
+        self.vmca() <= self.vmca_limit()
+    }
+
+    /// Fin area giving VMCA = 1.2 Vs with the current rudder (ft2).
+    pub fn s_vt_required(&self) -> f64 {
+        let v = self.vmca_limit();
+        2. * self.asymmetric_yawing_moment() / (RHO_SL * v * v * self.fin_power() / self.s_vt)
+    }
+
+    /// Yawing moment per unit dynamic pressure at full rudder (ft3).
+    fn fin_power(&self) -> f64 {
+        self.s_vt * self.l_vt * self.eta_v * self.c_lav * self.tau_r * self.delta_r_max
+    }
+}
@@ -0,0 +1,312 @@
+use crate::Params;
+use crate::components::{Aircraft, Item};
+use crate::components::method::Method;
+use crate::components::actual::ActualWeights;
+use crate::components::technology::Material;
+use crate::components::varying::*;
+use crate::contingency::{Contingency, Maturity, contingency_item};
+use crate::loading::{LoadingCondition, cg_limits};
+use crate::stability::Stability;
+use crate::aerodynamics::planform::Planform;
+use crate::aerodynamics::downwash::DownwashModel;
+use crate::aerodynamics::fuselage_moment::{FuselageMoment, FuselageStation};
+use crate::utils::kg_to_lb;
+use crate::utils::point::Point;
+
+const INERTIA_ITERATIONS: usize = 3;
+pub const NUM_PASSENGERS: f64 = 90.;
+pub const PASSENGERS_ABREAST: f64 = 6.;
+pub const NUM_ROWS: f64 = NUM_PASSENGERS / PASSENGERS_ABREAST;
+
+/// lb
+pub fn passenger_weight() -> f64 {
+    kg_to_lb(100.)
+}
+
+pub fn fuel(params: &Params) -> Fuel {
+    Fuel::new(
+        *params.get("w_fuel").expect("missing w_fuel"),
+        Point::new(
+            *params.get("x_cg_fuel").expect("missing x_cg_fuel"),
+            *params.get("y_cg_fuel").expect("missing y_cg_fuel"),
+            *params.get("z_cg_fuel").expect("missing z_cg_fuel"),
+        ),
+    )
+}
+
+pub fn cargo(params: &Params) -> Payload {
+    Payload::new(
+        *params.get("w_cargo").expect("missing w_cargo"),
+        Point::new(
+            *params.get("x_cg_cargo").expect("missing x_cg_cargo"),
+            *params.get("y_cg_cargo").expect("missing y_cg_cargo"),
+            *params.get("z_cg_cargo").expect("missing z_cg_cargo"),
+        ),
+    )
+}
+
+/// Checks the codes choosing a method or model and the calibration
+/// switches, which the components then take as valid.
+pub fn check_codes(params: &Params) -> Result<(), String> {
+    ActualWeights::check(params)?;
+    for (symbol, code) in params {
+        if symbol.starts_with("method_") {
+            Method::from_code(*code).map_err(|error| format!("{}: {}", symbol, error))?;
+        } else if symbol.starts_with("material_") {
+            Material::from_code(*code).map_err(|error| format!("{}: {}", symbol, error))?;
+        } else if symbol.starts_with("maturity_") {
+            Maturity::from_code(*code).map_err(|error| format!("{}: {}", symbol, error))?;
+        }
+    }
+    if let Some(code) = params.get("downwash_model") {
+        DownwashModel::from_code(*code)?;
+    }
+    Ok(())
+}
+
+// parameters that duplicate or derive from others, set from them instead
+pub const DEPENDENT: [(&str, &str); 4] = [
+    ("s_h", "s_ht"),
+    ("wing_span", "s_w and ar"),
+    ("b", "s_w and ar"),
+    ("c_bar", "s_w and ar"),
+];
+
+/// Checks a parameter can be varied by a sweep or an optimisation: it is
+/// given and is not set from others.
+pub fn check_varied(params: &Params, symbol: &str) -> Result<(), String> {
+    if let Some((symbol, from)) = DEPENDENT.iter().find(|(dependent, _)| *dependent == symbol) {
+        return Err(format!("{} follows from {}, vary those instead", symbol, from));
+    }
+    if !params.contains_key(symbol) {
+        return Err(format!("{} is not a parameter", symbol));
+    }
+    Ok(())
+}
+
+/// Sets the parameters that duplicate or derive from a changed one, when
+/// given: the tailplane area of the stability model, and the wing span and
+/// MAC, the aspect ratio being held when the wing area changes.
+pub fn update_dependents(params: &mut Params, symbol: &str) {
+    let values = match symbol {
+        "s_ht" => vec![("s_h", params["s_ht"])],
+        "s_w" | "ar" => {
+            let wing = Planform::wing(params);
+            vec![("wing_span", wing.span()), ("b", wing.span()), ("c_bar", wing.mac())]
+        }
+        _ => vec![],
+    };
+    for (symbol, value) in values {
+        if let Some(old) = params.get_mut(symbol) {
+            *old = value;
+        }
+    }
+}
+
+/// Varying loads of each loading condition.
+pub fn load_sets(params: &Params) -> Vec<(&'static str, Vec<Item>)> {
+    let pilots = Pilots::new(2., kg_to_lb(85.));
+    let crew = Crew::new(3., kg_to_lb(85.));
+    let fuel = fuel(params);
+
+    let mut load_sets = vec![
+        ("empty", vec![]),
+        ("operating empty", vec![pilots.item(), crew.item()]),
+        ("operating empty + fuel", vec![pilots.item(), crew.item(), fuel.item()]),
+    ];
+    for (name, name_fuel, load_case) in [
+        ("passengers front", "passengers front + fuel", LoadCase::Front),
+        ("passengers rear", "passengers rear + fuel", LoadCase::Rear),
+        ("passengers center", "passengers center + fuel", LoadCase::Center),
+    ] {
+        let passengers = Passengers::new(NUM_PASSENGERS, passenger_weight(), load_case);
+        load_sets.push((name, vec![pilots.item(), crew.item(), passengers.item()]));
+        load_sets.push((name_fuel, vec![pilots.item(), crew.item(), passengers.item(), fuel.item()]));
+    }
+    // asymmetric loads
+    let passengers = Passengers::new(NUM_PASSENGERS, passenger_weight(), LoadCase::Center);
+    let cargo = cargo(params);
+    let mut fuel_imbalance = vec![pilots.item(), crew.item(), passengers.item()];
+    fuel_imbalance.extend(fuel.wing_tanks(
+        *params.get("y_tank").expect("missing y_tank"),
+        *params.get("w_fuel_imbalance").expect("missing w_fuel_imbalance"),
+    ));
+    load_sets.push(("passengers center + fuel imbalance", fuel_imbalance));
+    load_sets.push(("passengers center + cargo + fuel", vec![
+        pilots.item(), crew.item(), passengers.item(), cargo.item(), fuel.item(),
+    ]));
+    load_sets
+}
+
+/// Weight and balance of the aircraft described by a set of parameters:
+/// empty weight, loading conditions and stability.
+pub struct Evaluation {
+    aircraft: Aircraft,
+    w_dg: f64, // design gross weight (lb)
+    conditions: Vec<LoadingCondition>,
+    stability: Stability,
+    x_le_mac: f64, // leading edge of the wing mean aerodynamic chord (ft)
+    i_y: f64, // yawing moment of inertia used by the flight controls (lb.ft2)
+    radii_of_gyration: (f64, f64), // pitch and yaw radii of gyration used by the tailplane (ft)
+    calibrations: Vec<(String, f64)>, // k_calibration_<item> derived from the actual weights
+}
+
+impl Evaluation {
+    /// - stations: fuselage stations for Multhopp's fuselage moment, Gilruth's otherwise
+    pub fn new(params: &Params, stations: Option<&[FuselageStation]>) -> Self {
+        let mut aircraft = Aircraft::new(params);
+        let w_dg = params.get("w_dg").copied().unwrap_or(kg_to_lb(38249.));
+        let wing_planform = Planform::wing(params);
+        let load_sets = load_sets(params);
+        let contingency = Contingency::new(params);
+        let loading_conditions = |aircraft: &Aircraft| -> Vec<LoadingCondition> {
+            let mut empty_items = aircraft.items(w_dg);
+            empty_items.push(contingency_item(&contingency.lines(&empty_items)));
+            load_sets.iter()
+                .map(|(name, loads)| {
+                    LoadingCondition::new(name, empty_items.iter().chain(loads).cloned().collect())
+                })
+                .collect()
+        };
+
+        // the flight controls and tailplane estimates use the inertia of the
+        // heaviest condition, which depends on their own weights
+        let mut i_y = 0.;
+        let mut radii_of_gyration = (0., 0.);
+        for _ in 0..INERTIA_ITERATIONS {
+            let conditions = loading_conditions(&aircraft);
+            let heaviest = conditions.iter()
+                .max_by(|a, b| a.weight().total_cmp(&b.weight()))
+                .expect("no loading condition");
+            let inertia = heaviest.inertia();
+            i_y = inertia.i_zz();
+            radii_of_gyration = (
+                f64::sqrt(inertia.i_yy() / heaviest.weight()),
+                f64::sqrt(inertia.i_zz() / heaviest.weight()),
+            );
+            aircraft.set_i_y(i_y);
+            aircraft.set_radii_of_gyration(radii_of_gyration.0, radii_of_gyration.1);
+        }
+        let calibrations = aircraft.calibrate(w_dg);
+        let conditions = loading_conditions(&aircraft);
+
+        let mut stability = Stability::new(params);
+        if let Some(stations) = stations {
+            stability.set_fuselage_moment(FuselageMoment::multhopp(stations.to_vec(), &wing_planform));
+        }
+
+        Self {
+            aircraft,
+            w_dg,
+            conditions,
+            stability,
+            x_le_mac: wing_planform.x_le_mac(),
+            i_y,
+            radii_of_gyration,
+            calibrations,
+        }
+    }
+
+    pub fn aircraft(&self) -> &Aircraft {
+        &self.aircraft
+    }
+
+    pub fn w_dg(&self) -> f64 {
+        self.w_dg
+    }
+
+    pub fn conditions(&self) -> &[LoadingCondition] {
+        &self.conditions
+    }
+
+    pub fn stability(&self) -> &Stability {
+        &self.stability
+    }
+
+    pub fn x_le_mac(&self) -> f64 {
+        self.x_le_mac
+    }
+
+    pub fn i_y(&self) -> f64 {
+        self.i_y
+    }
+
+    pub fn radii_of_gyration(&self) -> (f64, f64) {
+        self.radii_of_gyration
+    }
+
+    pub fn calibrations(&self) -> &[(String, f64)] {
+        &self.calibrations
+    }
+
+    /// Parameters with the derived calibration factors in place of the
+    /// items to calibrate, so that other evaluations keep them fixed.
+    pub fn calibrated(&self, params: &Params) -> Params {
+        let mut params: Params = params.iter()
+            .filter(|(symbol, _)| !symbol.starts_with("calibrate_"))
+            .map(|(symbol, value)| (symbol.clone(), *value))
+            .collect();
+        params.extend(self.calibrations.iter().cloned());
+        params
+    }
+
+    pub fn condition(&self, name: &str) -> &LoadingCondition {
+        self.conditions.iter()
+            .find(|condition| condition.name() == name)
+            .unwrap_or_else(|| panic!("missing loading condition {}", name))
+    }
+
+    /// Empty weight with contingency (lb)
+    pub fn empty_weight(&self) -> f64 {
+        self.condition("empty").weight()
+    }
+
+    /// Operating empty weight (lb)
+    pub fn oew(&self) -> f64 {
+        self.condition("operating empty").weight()
+    }
+
+    /// Weight of the heaviest loading condition (lb)
+    pub fn mtow(&self) -> f64 {
+        self.conditions.iter().map(|condition| condition.weight()).fold(0., f64::max)
+    }
+
+    /// CG of the operating empty aircraft (fraction of MAC)
+    pub fn oew_cg_mac(&self) -> f64 {
+        self.cg_mac(*self.condition("operating empty").cg().x())
+    }
+
+    /// Static margin of the operating empty aircraft (fraction of MAC)
+    pub fn static_margin(&self) -> f64 {
+        self.stability.static_margin(*self.condition("operating empty").cg().x())
+    }
+
+    /// - x_cg: horizontal CG (ft)
+    pub fn cg_mac(&self, x_cg: f64) -> f64 {
+        self.stability.cg_mac(x_cg, self.x_le_mac)
+    }
+
+    /// Most forward and most aft CG over the loading conditions (fraction of MAC)
+    pub fn cg_range_mac(&self) -> (f64, f64) {
+        let (fwd, aft) = cg_limits(&self.conditions);
+        (self.cg_mac(*fwd.cg().x()), self.cg_mac(*aft.cg().x()))
+    }
+
+    /// Forward and aft CG limits (fraction of MAC): cg_fwd_limit, the most
+    /// forward loading condition unless given, and cg_aft_limit, the neutral
+    /// point less static_margin_min (5% unless given) unless given.
+    pub fn cg_limits_mac(&self, params: &Params) -> (f64, f64) {
+        let static_margin_min = params.get("static_margin_min").copied().unwrap_or(0.05);
+        let fwd = params.get("cg_fwd_limit").copied().unwrap_or_else(|| self.cg_range_mac().0);
+        let aft = params.get("cg_aft_limit")
+            .copied()
+            .unwrap_or_else(|| self.cg_mac(self.stability.x_np()) - static_margin_min);
+        (fwd, aft)
+    }
+
+    /// Static margin at the most forward and the most aft CG (fraction of MAC)
+    pub fn static_margin_range(&self) -> (f64, f64) {
+        let (fwd, aft) = cg_limits(&self.conditions);
+        (self.stability.static_margin(*fwd.cg().x()), self.stability.static_margin(*aft.cg().x()))
+    }
+}
@@ -0,0 +1,259 @@
+use std::error::Error;
+use std::fs;
+use csv::Writer;
+use crate::Params;
+use crate::components::Item;
+use crate::contingency::Contingency;
+use crate::evaluation::Evaluation;
+use crate::lateral::LateralBalance;
+use crate::loading::cg_limits;
+use crate::utils::json::Json;
+use crate::utils::point::Point;
+
+/// Incremented when a member is renamed or removed, not when one is added.
+pub const SCHEMA_VERSION: f64 = 1.;
+
+fn point(p: Point<f64>) -> Json {
+    Json::object(vec![("x", Json::Number(*p.x())), ("y", Json::Number(*p.y())), ("z", Json::Number(*p.z()))])
+}
+
+fn item(item: &Item) -> Json {
+    Json::object(vec![
+        ("name", Json::string(item.name())),
+        ("weight", Json::Number(item.weight().get_val())),
+        ("cg", point(item.cg())),
+        ("moment", point(item.pos_times_weight())),
+        ("method", item.method().map_or(Json::Null, |method| Json::string(method.name()))),
+        ("technology", Json::Number(item.technology())),
+        ("estimate", Json::optional(item.estimate().map(|weight| weight.get_val()))),
+        ("calibration", Json::optional(item.calibration())),
+    ])
+}
+
+/// Full weight and balance state of one evaluation: the parameters used,
+/// the items and totals of each group, the contingency, the loading
+/// conditions and the stability. Weights in lb, lengths in ft, inertias in
+/// lb.ft2, CG and static margins relative to the MAC as fractions of it.
+pub fn document(params: &Params, evaluation: &Evaluation) -> Json {
+    let w_dg = evaluation.w_dg();
+    let aircraft = evaluation.aircraft();
+    let stability = evaluation.stability();
+
+    let mut symbols: Vec<&String> = params.keys().collect();
+    symbols.sort();
+    let inputs = symbols.into_iter().map(|symbol| (symbol.clone(), Json::Number(params[symbol]))).collect();
+
+    let groups = aircraft.groups(w_dg).into_iter()
+        .map(|(group, items)| {
+            let weight: f64 = items.iter().map(|item| item.weight().get_val()).sum();
+            let moment = items.iter()
+                .map(|item| item.pos_times_weight())
+                .fold(Point::new(0., 0., 0.), |a, b| a + b);
+            Json::object(vec![
+                ("name", Json::string(group)),
+                ("weight", Json::Number(weight)),
+                ("cg", point(moment / weight)),
+                ("moment", point(moment)),
+                ("items", Json::Array(items.iter().map(item).collect())),
+            ])
+        })
+        .collect();
+
+    let contingency_lines = Contingency::new(params).lines(&aircraft.items(w_dg));
+    let contingency = contingency_lines.iter()
+        .map(|line| Json::object(vec![
+            ("name", Json::string(line.name())),
+            ("ata", Json::optional(line.ata().map(f64::from))),
+            ("maturity", Json::string(line.maturity().name())),
+            ("weight", Json::Number(line.weight())),
+            ("contingency", Json::Number(line.contingency())),
+        ]))
+        .collect();
+
+    let lateral_balance = LateralBalance::new(params);
+    let conditions = evaluation.conditions().iter()
+        .map(|condition| {
+            let cg = condition.cg();
+            let inertia = condition.inertia();
+            Json::object(vec![
+                ("name", Json::string(condition.name())),
+                ("weight", Json::Number(condition.weight())),
+                ("cg", point(cg)),
+                ("moment", point(condition.moment())),
+                ("cg_mac", Json::Number(evaluation.cg_mac(*cg.x()))),
+                ("static_margin", Json::Number(stability.static_margin(*cg.x()))),
+                ("rolling_moment", Json::Number(lateral_balance.rolling_moment(condition))),
+                ("within_lateral_limit", Json::Bool(lateral_balance.within_limit(condition))),
+                ("inertia", Json::object(vec![
+                    ("i_xx", Json::Number(inertia.i_xx())),
+                    ("i_yy", Json::Number(inertia.i_yy())),
+                    ("i_zz", Json::Number(inertia.i_zz())),
+                    ("i_xz", Json::Number(inertia.i_xz())),
+                ])),
+            ])
+        })
+        .collect();
+
+    let (fwd, aft) = cg_limits(evaluation.conditions());
+    let (cg_fwd_mac, cg_aft_mac) = evaluation.cg_range_mac();
+    let (static_margin_fwd, static_margin_aft) = evaluation.static_margin_range();
+    Json::object(vec![
+        ("schema_version", Json::Number(SCHEMA_VERSION)),
+        ("inputs", Json::Object(inputs)),
+        ("totals", Json::object(vec![
+            ("w_dg", Json::Number(w_dg)),
+            ("empty_weight", Json::Number(evaluation.empty_weight())),
+            ("contingency", Json::Number(contingency_lines.iter().map(|line| line.contingency()).sum())),
+            ("operating_empty_weight", Json::Number(evaluation.oew())),
+            ("max_condition_weight", Json::Number(evaluation.mtow())),
+        ])),
+        ("groups", Json::Array(groups)),
+        ("contingency", Json::Array(contingency)),
+        ("conditions", Json::Array(conditions)),
+        ("stability", Json::object(vec![
+            ("x_le_mac", Json::Number(evaluation.x_le_mac())),
+            ("x_np", Json::Number(stability.x_np())),
+            ("c_maf", Json::Number(stability.c_maf())),
+            ("cg_mac", Json::Number(evaluation.oew_cg_mac())),
+            ("static_margin", Json::Number(evaluation.static_margin())),
+            ("forward_condition", Json::string(fwd.name())),
+            ("aft_condition", Json::string(aft.name())),
+            ("cg_fwd_mac", Json::Number(cg_fwd_mac)),
+            ("cg_aft_mac", Json::Number(cg_aft_mac)),
+            ("static_margin_fwd", Json::Number(static_margin_fwd)),
+            ("static_margin_aft", Json::Number(static_margin_aft)),
+        ])),
+    ])
+}
+
+pub fn write_json(path: &str, document: &Json) -> Result<(), Box<dyn Error>> {
+    fs::write(path, format!("{}\n", document))?;
+    Ok(())
+}
+
+/// One row per value with columns key and value, the key being its path in
+/// the JSON document, e.g. groups/wings/items/wings structure/weight.
+pub fn write_csv(path: &str, document: &Json) -> Result<(), Box<dyn Error>> {
+    let mut writer = Writer::from_path(path)?;
+    writer.write_record(["key", "value"])?;
+    for (key, value) in document.flatten() {
+        writer.write_record([key, value])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample_params;
+
+    /// Member paths in order, with * for the elements of arrays, without the
+    /// members of the inputs which are the parameters.
+    fn member_paths(json: &Json, path: &str, paths: &mut Vec<String>) {
+        match json {
+            Json::Object(members) => {
+                for (key, value) in members {
+                    let path = if path.is_empty() { key.clone() } else { format!("{}/{}", path, key) };
+                    if !paths.contains(&path) {
+                        paths.push(path.clone());
+                    }
+                    if path != "inputs" {
+                        member_paths(value, &path, paths);
+                    }
+                }
+            }
+            Json::Array(elements) => {
+                for element in elements {
+                    member_paths(element, &format!("{}/*", path), paths);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // renaming or removing any of them needs a new SCHEMA_VERSION
+    const MEMBERS: [&str; 72] = [
+        "schema_version",
+        "inputs",
+        "totals",
+        "totals/w_dg",
+        "totals/empty_weight",
+        "totals/contingency",
+        "totals/operating_empty_weight",
+        "totals/max_condition_weight",
+        "groups",
+        "groups/*/name",
+        "groups/*/weight",
+        "groups/*/cg",
+        "groups/*/cg/x",
+        "groups/*/cg/y",
+        "groups/*/cg/z",
+        "groups/*/moment",
+        "groups/*/moment/x",
+        "groups/*/moment/y",
+        "groups/*/moment/z",
+        "groups/*/items",
+        "groups/*/items/*/name",
+        "groups/*/items/*/weight",
+        "groups/*/items/*/cg",
+        "groups/*/items/*/cg/x",
+        "groups/*/items/*/cg/y",
+        "groups/*/items/*/cg/z",
+        "groups/*/items/*/moment",
+        "groups/*/items/*/moment/x",
+        "groups/*/items/*/moment/y",
+        "groups/*/items/*/moment/z",
+        "groups/*/items/*/method",
+        "groups/*/items/*/technology",
+        "groups/*/items/*/estimate",
+        "groups/*/items/*/calibration",
+        "contingency",
+        "contingency/*/name",
+        "contingency/*/ata",
+        "contingency/*/maturity",
+        "contingency/*/weight",
+        "contingency/*/contingency",
+        "conditions",
+        "conditions/*/name",
+        "conditions/*/weight",
+        "conditions/*/cg",
+        "conditions/*/cg/x",
+        "conditions/*/cg/y",
+        "conditions/*/cg/z",
+        "conditions/*/moment",
+        "conditions/*/moment/x",
+        "conditions/*/moment/y",
+        "conditions/*/moment/z",
+        "conditions/*/cg_mac",
+        "conditions/*/static_margin",
+        "conditions/*/rolling_moment",
+        "conditions/*/within_lateral_limit",
+        "conditions/*/inertia",
+        "conditions/*/inertia/i_xx",
+        "conditions/*/inertia/i_yy",
+        "conditions/*/inertia/i_zz",
+        "conditions/*/inertia/i_xz",
+        "stability",
+        "stability/x_le_mac",
+        "stability/x_np",
+        "stability/c_maf",
+        "stability/cg_mac",
+        "stability/static_margin",
+        "stability/forward_condition",
+        "stability/aft_condition",
+        "stability/cg_fwd_mac",
+        "stability/cg_aft_mac",
+        "stability/static_margin_fwd",
+        "stability/static_margin_aft",
+    ];
+
+    #[test]
+    fn document_members() {
+        let params = sample_params();
+        let evaluation = Evaluation::new(&params, None);
+        let mut paths = vec![];
+        member_paths(&document(&params, &evaluation), "", &mut paths);
+        assert_eq!(paths, MEMBERS);
+    }
+}
@@ -0,0 +1,97 @@
+use crate::Params;
+use crate::utils::point::Point;
+use crate::utils::in_to_ft;
+
+// placement requirements
+const NOSE_LOAD_MIN: f64 = 0.08; // fraction of the weight on the nose gear at aft CG
+const NOSE_LOAD_MAX: f64 = 0.15; // fraction of the weight on the nose gear at forward CG
+const TIP_BACK_MIN: f64 = 15.; // degrees
+const TURNOVER_MAX: f64 = 55.; // degrees
+
+/// Landing gear placement relative to the CG range, gear legs static on the
+/// ground. The gear CGs are taken at mid strut length.
+pub struct GearGeometry {
+    x_m: f64, // main gear ground contact position (ft)
+    x_n: f64, // nose gear ground contact position (ft)
+    z_ground: f64, // ground height below the static aircraft (ft)
+    t_m: f64, // main gear track (ft)
+    tail_strike: Point<f64>, // lowest point of the aft fuselage (ft)
+}
+
+impl GearGeometry {
+    pub fn new(params: &Params) -> Self {
+        let z_cg_main_landing_gear = *params.get("z_cg_main_landing_gear").expect("missing z_cg_main_landing_gear");
+        let l_m = *params.get("l_m").expect("missing l_m");
+        Self {
+            x_m: *params.get("x_cg_main_landing_gear").expect("missing x_cg_main_landing_gear"),
+            x_n: *params.get("x_cg_nose_landing_gear").expect("missing x_cg_nose_landing_gear"),
+            z_ground: z_cg_main_landing_gear - 0.5 * in_to_ft(l_m),
+            t_m: *params.get("t_m").expect("missing t_m"),
+            tail_strike: Point::new(
+                *params.get("x_tail_strike").expect("missing x_tail_strike"),
+                0.,
+                *params.get("z_tail_strike").expect("missing z_tail_strike"),
+            ),
+        }
+    }
+
+    pub fn x_m(&self) -> f64 {
+        self.x_m
+    }
+
+    pub fn x_n(&self) -> f64 {
+        self.x_n
+    }
+
+    /// Height of the CG above the ground (ft).
+    pub fn cg_height(&self, cg: Point<f64>) -> f64 {
+        cg.z() - self.z_ground
+    }
+
+    /// Fraction of the aircraft weight carried by the nose gear.
+    pub fn nose_load_fraction(&self, cg: Point<f64>) -> f64 {
+        (self.x_m - cg.x()) / (self.x_m - self.x_n)
+    }
+
+    /// Angle between the vertical through the main gear contact and the line
+    /// from the contact to the CG (degrees).
+    pub fn tip_back_angle(&self, cg: Point<f64>) -> f64 {
+        f64::atan((self.x_m - cg.x()).abs() / self.cg_height(cg)).to_degrees()
+    }
+
+    /// Fuselage rotation angle when the aft fuselage touches the ground, main
+    /// gear legs uncompressed (degrees).
+    pub fn tail_strike_angle(&self) -> f64 {
+        f64::atan((self.tail_strike.z() - self.z_ground) / (self.tail_strike.x() - self.x_m)).to_degrees()
+    }
+
+    /// Lateral turnover angle about the line joining the nose and one main
+    /// gear contact (degrees).
+    pub fn turnover_angle(&self, cg: Point<f64>) -> f64 {
+        let delta = f64::atan(0.5 * self.t_m / (self.x_m - self.x_n));
+        f64::atan(self.cg_height(cg) / ((cg.x() - self.x_n) * delta.sin())).to_degrees()
+    }
+
+    /// Lists the requirements missed at the ends of the CG range.
+    pub fn violations(&self, cg_fwd: Point<f64>, cg_aft: Point<f64>) -> Vec<String> {
+        let mut violations = Vec::new();
+        let nose_load_fwd = self.nose_load_fraction(cg_fwd);
+        if nose_load_fwd > NOSE_LOAD_MAX {
+            violations.push(format!("nose gear load at forward CG {:.3} above {}", nose_load_fwd, NOSE_LOAD_MAX));
+        }
+        let nose_load_aft = self.nose_load_fraction(cg_aft);
+        if nose_load_aft < NOSE_LOAD_MIN {
+            violations.push(format!("nose gear load at aft CG {:.3} below {}", nose_load_aft, NOSE_LOAD_MIN));
+        }
+        let tip_back = self.tip_back_angle(cg_aft);
+        let tip_back_min = f64::max(TIP_BACK_MIN, self.tail_strike_angle());
+        if cg_aft.x() >= &self.x_m || tip_back < tip_back_min {
+            violations.push(format!("tip-back angle at aft CG {:.1} deg below {:.1} deg", tip_back, tip_back_min));
+        }
+        let turnover = f64::max(self.turnover_angle(cg_fwd), self.turnover_angle(cg_aft));
+        if turnover > TURNOVER_MAX {
+            violations.push(format!("turnover angle {:.1} deg above {} deg", turnover, TURNOVER_MAX));
+        }
+        violations
+    }
+}
@@ -0,0 +1,86 @@
+use std::error::Error;
+use csv::Writer;
+use crate::Params;
+use crate::utils::point::Point;
+use crate::ground_handling::GearGeometry;
+
+const G: f64 = 32.174; // ft/s2
+
+/// Gear reactions for the static and braked ground conditions, the aircraft
+/// at rest on level ground.
+pub struct GroundLoads {
+    n_mss: f64, // Number of main gear shock struts
+    a_brake: f64, // braking deceleration (ft/s2)
+}
+
+impl GroundLoads {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            n_mss: *params.get("n_mss").expect("missing n_mss"),
+            a_brake: *params.get("a_brake").expect("missing a_brake"),
+        }
+    }
+
+    /// - weight: aircraft weight (lb)
+    /// - cg: aircraft CG
+    pub fn load_case(&self, gear: &GearGeometry, condition: &str, weight: f64, cg: Point<f64>) -> GearLoadCase {
+        let wheel_base = gear.x_m() - gear.x_n();
+        let nose_static = weight * (gear.x_m() - cg.x()) / wheel_base;
+        // the braking force at the main wheels pitches the aircraft nose down
+        let transfer = weight * self.a_brake / G * gear.cg_height(cg) / wheel_base;
+        GearLoadCase {
+            condition: condition.to_string(),
+            weight,
+            x_cg: *cg.x(),
+            nose_static,
+            main_static: (weight - nose_static) / self.n_mss,
+            nose_braking: nose_static + transfer,
+            main_braking: (weight - nose_static - transfer) / self.n_mss,
+            main_drag: weight * self.a_brake / G / self.n_mss,
+        }
+    }
+}
+
+/// Vertical and drag reactions for one condition, main gear loads per leg (lb).
+pub struct GearLoadCase {
+    condition: String,
+    weight: f64,
+    x_cg: f64,
+    nose_static: f64,
+    main_static: f64,
+    nose_braking: f64,
+    main_braking: f64,
+    main_drag: f64,
+}
+
+impl GearLoadCase {
+    pub fn nose_braking(&self) -> f64 {
+        self.nose_braking
+    }
+
+    pub fn main_static(&self) -> f64 {
+        self.main_static
+    }
+}
+
+pub fn write_load_table(path: &str, load_cases: &[GearLoadCase]) -> Result<(), Box<dyn Error>> {
+    let mut writer = Writer::from_path(path)?;
+    writer.write_record([
+        "condition", "weight", "x_cg",
+        "nose_static", "main_static", "nose_braking", "main_braking", "main_drag",
+    ])?;
+    for load_case in load_cases {
+        writer.write_record([
+            load_case.condition.clone(),
+            load_case.weight.to_string(),
+            load_case.x_cg.to_string(),
+            load_case.nose_static.to_string(),
+            load_case.main_static.to_string(),
+            load_case.nose_braking.to_string(),
+            load_case.main_braking.to_string(),
+            load_case.main_drag.to_string(),
+        ])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use crate::components::Item;
+use crate::utils::point::Point;
+
+/// Mass moments and product of inertia about the CG of a set of items, in
+/// body axes (lb.ft2, weight based as in the Raymer equations). The aircraft
+/// is symmetric so Ixy and Iyz are left out.
+pub struct Inertia {
+    i_xx: f64, // rolling
+    i_yy: f64, // pitching
+    i_zz: f64, // yawing
+    i_xz: f64,
+}
+
+impl Inertia {
+    /// Point masses plus the own inertia of the distributed items, each
+    /// taken as a uniform box of its extent.
+    pub fn new(items: &[Item]) -> Self {
+        let weight: f64 = items.iter().map(|item| item.weight().get_val()).sum();
+        let cg = items.iter()
+            .map(|item| item.pos_times_weight())
+            .fold(Point::new(0., 0., 0.), |a, b| a + b) / weight;
+        let mut inertia = Self { i_xx: 0., i_yy: 0., i_zz: 0., i_xz: 0. };
+        for item in items {
+            let w = item.weight().get_val();
+            let dx = item.cg().x() - cg.x();
+            let dy = item.cg().y() - cg.y();
+            let dz = item.cg().z() - cg.z();
+            let a = *item.extent().x();
+            let b = *item.extent().y();
+            let c = *item.extent().z();
+            inertia.i_xx += w * (dy * dy + dz * dz + (b * b + c * c) / 12.);
+            inertia.i_yy += w * (dx * dx + dz * dz + (a * a + c * c) / 12.);
+            inertia.i_zz += w * (dx * dx + dy * dy + (a * a + b * b) / 12.);
+            inertia.i_xz += w * dx * dz;
+        }
+        inertia
+    }
+
+    pub fn i_xx(&self) -> f64 {
+        self.i_xx
+    }
+
+    pub fn i_yy(&self) -> f64 {
+        self.i_yy
+    }
+
+    pub fn i_zz(&self) -> f64 {
+        self.i_zz
+    }
+
+    pub fn i_xz(&self) -> f64 {
+        self.i_xz
+    }
+}
@@ -0,0 +1,33 @@
+use crate::Params;
+use crate::loading::LoadingCondition;
+
+/// Lateral balance against the fuel imbalance limit, taken as the largest
+/// rolling moment the aircraft may carry about its centerline.
+pub struct LateralBalance {
+    w_imbalance_max: f64, // maximum fuel weight difference between the wing tanks (lb)
+    y_tank: f64, // lateral position of each wing tank (ft)
+}
+
+impl LateralBalance {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            w_imbalance_max: *params.get("w_imbalance_max").expect("missing w_imbalance_max"),
+            y_tank: *params.get("y_tank").expect("missing y_tank"),
+        }
+    }
+
+    /// Largest acceptable rolling moment (lb.ft).
+    pub fn rolling_moment_limit(&self) -> f64 {
+        self.w_imbalance_max * self.y_tank
+    }
+
+    /// Rolling moment of the loads about the centerline, positive right wing
+    /// down (lb.ft).
+    pub fn rolling_moment(&self, condition: &LoadingCondition) -> f64 {
+        *condition.moment().y()
+    }
+
+    pub fn within_limit(&self, condition: &LoadingCondition) -> bool {
+        self.rolling_moment(condition).abs() <= self.rolling_moment_limit()
+    }
+}
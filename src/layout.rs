@@ -0,0 +1,234 @@
+use std::error::Error;
+use std::fs;
+use crate::Params;
+use crate::components::Item;
+use crate::aerodynamics::planform::Planform;
+use crate::plot::escape;
+use crate::utils::point::Point;
+
+const WIDTH: f64 = 960.; // px
+const MARGIN: f64 = 30.; // around and between the views (px)
+const MAX_MARKER: f64 = 14.; // radius of the marker of the heaviest item (px)
+const NOSE: f64 = 0.12; // fraction of the fuselage length
+const TAIL_CONE: f64 = 0.3; // fraction of the fuselage length
+const OUTLINE_POINTS: usize = 8; // per curve of the fuselage outline
+
+/// Body frame coordinates (ft) to the horizontal and vertical ones of a view.
+type Projection = fn(Point<f64>) -> (f64, f64);
+
+/// Maps body frame coordinates (ft) of one view to the drawing (px), the
+/// vertical axis pointing up.
+struct View {
+    left: f64, // px
+    top: f64, // px
+    h_min: f64, // ft
+    v_max: f64, // ft
+    scale: f64, // px per ft
+}
+
+impl View {
+    fn point(&self, (h, v): (f64, f64)) -> (f64, f64) {
+        (self.left + (h - self.h_min) * self.scale, self.top + (self.v_max - v) * self.scale)
+    }
+
+    fn polygon(&self, points: &[(f64, f64)], fill: &str) -> String {
+        let points: Vec<String> = points.iter()
+            .map(|p| {
+                let (x, y) = self.point(*p);
+                format!("{:.1},{:.1}", x, y)
+            })
+            .collect();
+        format!("<polygon points=\"{}\" fill=\"{}\" stroke=\"black\" stroke-width=\"0.8\"/>\n", points.join(" "), fill)
+    }
+}
+
+/// Leading and trailing edges of one side of a planform, seen from above:
+/// (x, y) from the root leading edge round to the root trailing edge.
+fn half_planform(planform: &Planform) -> Vec<(f64, f64)> {
+    let root = planform.root();
+    let (x, y) = (*root.x(), *root.y());
+    let half_span = 0.5 * planform.span();
+    let x_tip = x + half_span * planform.sweep_le().tan();
+    vec![
+        (x, y),
+        (x_tip, y + half_span),
+        (x_tip + planform.tip_chord(), y + half_span),
+        (x + planform.root_chord(), y),
+    ]
+}
+
+/// Simplified outline of the fuselage, (x, v) with v the half width or, in
+/// side view, the height above the centreline of the upper and lower sides.
+fn fuselage_outline(l: f64, d: f64, side: bool) -> Vec<(f64, f64)> {
+    let r = 0.5 * d;
+    let (x_nose, x_tail) = (NOSE * l, (1. - TAIL_CONE) * l);
+    // the tail cone sweeps up in side view, closing on a small radius
+    let (upper_end, lower_end) = if side { (0.6 * r, 0.3 * r) } else { (0.15 * r, -0.15 * r) };
+    let mut upper = vec![];
+    let mut lower = vec![];
+    for i in 0..=OUTLINE_POINTS {
+        let t = i as f64 / OUTLINE_POINTS as f64;
+        let radius = r * f64::sqrt(1. - (1. - t).powi(2));
+        upper.push((x_nose * t, radius));
+        lower.push((x_nose * t, -radius));
+    }
+    for i in 0..=OUTLINE_POINTS {
+        let t = i as f64 / OUTLINE_POINTS as f64;
+        let x = x_tail + (l - x_tail) * t;
+        upper.push((x, r + (upper_end - r) * t));
+        lower.push((x, -r + (lower_end + r) * t));
+    }
+    lower.reverse();
+    upper.extend(lower);
+    upper
+}
+
+/// Side, top and front views of the aircraft drawn to the same scale, with
+/// the CG of each item marked by a circle whose area is proportional to its
+/// weight, and the CG of all of them by a cross.
+pub struct ThreeView {
+    l: f64, // fuselage length (ft)
+    d: f64, // fuselage diameter (ft)
+    wing: Planform,
+    horizontal_tailplane: Planform,
+    root_vt: Point<f64>, // leading edge of the fin root chord
+    fin_height_vt: f64,
+    chord_55_vt: f64, // chord at 55% of the fin height
+    sweep_vt: f64, // quarter chord sweep
+    lambda_v: f64, // taper ratio, 1 unless given
+}
+
+impl ThreeView {
+    pub fn new(params: &Params) -> Self {
+        Self {
+            l: *params.get("l").expect("missing l"),
+            d: *params.get("d").expect("missing d"),
+            wing: Planform::wing(params),
+            horizontal_tailplane: Planform::horizontal_tailplane(params),
+            root_vt: Point::new(
+                *params.get("x_root_vt").expect("missing x_root_vt"),
+                *params.get("y_root_vt").expect("missing y_root_vt"),
+                *params.get("z_root_vt").expect("missing z_root_vt"),
+            ),
+            fin_height_vt: *params.get("fin_height_vt").expect("missing fin_height_vt"),
+            chord_55_vt: *params.get("chord_55_vt").expect("missing chord_55_vt"),
+            sweep_vt: *params.get("sweep_vt").expect("missing sweep_vt"),
+            lambda_v: params.get("lambda_v").copied().unwrap_or(1.),
+        }
+    }
+
+    /// Fin seen from the side, (x, z) from the root leading edge round to the
+    /// root trailing edge.
+    fn fin(&self) -> Vec<(f64, f64)> {
+        let c_root = self.chord_55_vt / (1. - 0.55 * (1. - self.lambda_v));
+        let c_tip = self.lambda_v * c_root;
+        let (x, z, h) = (*self.root_vt.x(), *self.root_vt.z(), self.fin_height_vt);
+        let x_tip = x + 0.25 * c_root + h * self.sweep_vt.tan() - 0.25 * c_tip;
+        vec![(x, z), (x_tip, z + h), (x_tip + c_tip, z + h), (x + c_root, z)]
+    }
+
+    pub fn to_svg(&self, items: &[Item]) -> String {
+        let half_span = 0.5 * self.wing.span() + self.wing.root().y();
+        let fin = self.fin();
+        let fin_top = fin[1].1;
+        // extents of the views (ft)
+        let x_min = items.iter().map(|item| *item.cg().x()).fold(0., f64::min);
+        let x_max = fin.iter()
+            .map(|p| p.0)
+            .chain(half_planform(&self.horizontal_tailplane).iter().map(|p| p.0))
+            .chain(items.iter().map(|item| *item.cg().x()))
+            .fold(self.l, f64::max);
+        let y_max = items.iter().map(|item| item.cg().y().abs()).fold(half_span, f64::max);
+        let z_max = items.iter().map(|item| *item.cg().z()).fold(fin_top.max(0.5 * self.d), f64::max);
+        let z_min = items.iter().map(|item| *item.cg().z()).fold(-0.5 * self.d, f64::min);
+
+        // top view above the side view, front view to the right of the side view
+        let scale = (WIDTH - 3. * MARGIN) / (x_max - x_min + 2. * y_max);
+        let top_view = View { left: MARGIN, top: MARGIN, h_min: x_min, v_max: y_max, scale };
+        let side_top = 2. * MARGIN + 2. * y_max * scale;
+        let side_view = View { left: MARGIN, top: side_top, h_min: x_min, v_max: z_max, scale };
+        let front_left = 2. * MARGIN + (x_max - x_min) * scale;
+        // seen from the front, the right side is on the left
+        let front_view = View { left: front_left, top: side_top, h_min: -y_max, v_max: z_max, scale };
+        let height = side_top + (z_max - z_min) * scale + MARGIN;
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1:.0}\" viewBox=\"0 0 {0} {1:.0}\" \
+             font-family=\"sans-serif\" font-size=\"11\">\n<rect width=\"{0}\" height=\"{1:.0}\" fill=\"white\"/>\n",
+            WIDTH, height,
+        );
+        for (view, name) in [(&top_view, "top"), (&side_view, "side"), (&front_view, "front")] {
+            let (x, y) = view.point((view.h_min, view.v_max));
+            svg += &format!("<text x=\"{:.1}\" y=\"{:.1}\">{} view</text>\n", x, y - 8., name);
+        }
+        let fill = "#e8e8e8";
+        let mirror = |points: &[(f64, f64)]| -> Vec<(f64, f64)> { points.iter().map(|(h, v)| (*h, -v)).collect() };
+
+        // top view, y up so that the right side is above the centreline
+        for planform in [&self.wing, &self.horizontal_tailplane] {
+            let half = half_planform(planform);
+            svg += &top_view.polygon(&half, fill);
+            svg += &top_view.polygon(&mirror(&half), fill);
+        }
+        svg += &top_view.polygon(&fuselage_outline(self.l, self.d, false), fill);
+        let (x_fin, c_fin) = (fin[0].0, fin[3].0 - fin[0].0);
+        svg += &top_view.polygon(&[(x_fin, 0.1), (x_fin + c_fin, 0.1), (x_fin + c_fin, -0.1), (x_fin, -0.1)], fill);
+
+        // side view
+        svg += &side_view.polygon(&fin, fill);
+        svg += &side_view.polygon(&fuselage_outline(self.l, self.d, true), fill);
+        for planform in [&self.wing, &self.horizontal_tailplane] {
+            let half = half_planform(planform);
+            let z = *planform.root().z();
+            let (x_le, x_te) = (half[0].0, half[1].0.max(half[2].0).max(half[3].0));
+            svg += &side_view.polygon(&[(x_le, z + 0.2), (x_te, z + 0.2), (x_te, z - 0.2), (x_le, z - 0.2)], fill);
+        }
+
+        // front view
+        for planform in [&self.wing, &self.horizontal_tailplane] {
+            let (y, z) = (*planform.root().y() + 0.5 * planform.span(), *planform.root().z());
+            svg += &front_view.polygon(&[(-y, z + 0.2), (y, z + 0.2), (y, z - 0.2), (-y, z - 0.2)], fill);
+        }
+        let (cx, cy) = front_view.point((0., 0.));
+        svg += &format!(
+            "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"{}\" stroke=\"black\" stroke-width=\"0.8\"/>\n",
+            cx, cy, 0.5 * self.d * scale, fill,
+        );
+        let (z_fin, h_fin) = (*self.root_vt.z(), self.fin_height_vt);
+        svg += &front_view.polygon(&[(-0.1, z_fin), (-0.1, z_fin + h_fin), (0.1, z_fin + h_fin), (0.1, z_fin)], fill);
+
+        // item markers
+        let w_max = items.iter().map(|item| item.weight().get_val()).fold(0., f64::max);
+        let total: f64 = items.iter().map(|item| item.weight().get_val()).sum();
+        let cg = items.iter().map(|item| item.pos_times_weight()).fold(Point::new(0., 0., 0.), |a, b| a + b) / total;
+        let views: [(&View, Projection); 3] = [
+            (&top_view, |p| (*p.x(), *p.y())),
+            (&side_view, |p| (*p.x(), *p.z())),
+            (&front_view, |p| (-p.y(), *p.z())),
+        ];
+        for (view, project) in views {
+            for item in items.iter().filter(|item| item.weight().get_val() > 0.) {
+                let (x, y) = view.point(project(item.cg()));
+                let r = MAX_MARKER * f64::sqrt(item.weight().get_val() / w_max);
+                svg += &format!(
+                    "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"#d62728\" fill-opacity=\"0.5\" stroke=\"#d62728\">\
+                     <title>{}: {:.0} lb</title></circle>\n",
+                    x, y, r, escape(item.name()), item.weight().get_val(),
+                );
+            }
+            let (x, y) = view.point(project(cg));
+            svg += &format!(
+                "<path d=\"M {0:.1} {1:.1} h 16 M {2:.1} {3:.1} v 16\" stroke=\"black\" stroke-width=\"2\">\
+                 <title>CG: {4:.0} lb</title></path>\n",
+                x - 8., y, x, y - 8., total,
+            );
+        }
+        svg += "</svg>\n";
+        svg
+    }
+}
+
+pub fn write_three_view(path: &str, three_view: &ThreeView, items: &[Item]) -> Result<(), Box<dyn Error>> {
+    fs::write(path, three_view.to_svg(items))?;
+    Ok(())
+}
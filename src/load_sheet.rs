@@ -0,0 +1,512 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use csv::Reader;
+use crate::Params;
+use crate::components::Item;
+use crate::components::varying::{LoadCase, Passengers, Payload, Fuel};
+use crate::evaluation::{Evaluation, NUM_ROWS, PASSENGERS_ABREAST, passenger_weight, fuel};
+use crate::plot::escape;
+use crate::utils::point::Point;
+
+/// Reads the loading of one flight, in the format of the parameters files:
+/// - pax_zone_<n>: passengers seated in cabin zone n
+/// - bags_hold_<n>, cargo_hold_<n>: baggage and cargo in hold n (lb)
+/// - fuel_takeoff, fuel_trip: fuel at take-off and burnt to landing (lb)
+pub fn read_load(path: &str) -> Result<Params, Box<dyn Error>> {
+    let mut load = Params::new();
+    let mut reader = Reader::from_path(path)?;
+    for result in reader.records() {
+        let record = result?;
+        load.insert(record[0].to_string(), record[1].parse::<f64>()?);
+    }
+    Ok(load)
+}
+
+/// Reads a table of N columns with a header, sorted by the first column.
+fn read_table<const N: usize>(path: &str) -> Result<Vec<[f64; N]>, Box<dyn Error>> {
+    let mut rows: Vec<[f64; N]> = Vec::new();
+    let mut reader = Reader::from_path(path).map_err(|e| format!("{}: {}", path, e))?;
+    for result in reader.records() {
+        let record = result?;
+        let mut row = [0.; N];
+        for (i, value) in row.iter_mut().enumerate() {
+            *value = record.get(i).ok_or_else(|| format!("{}: expected {} columns", path, N))?.parse::<f64>()?;
+        }
+        if rows.last().is_some_and(|last| last[0] >= row[0]) {
+            return Err(format!("{}: rows not sorted by increasing {}", path, &reader.headers()?[0]).into());
+        }
+        rows.push(row);
+    }
+    if rows.is_empty() {
+        return Err(format!("{}: empty table", path).into());
+    }
+    Ok(rows)
+}
+
+/// Linear interpolation between the rows of a table on the first column,
+/// none outside it.
+fn interpolate<const N: usize>(rows: &[[f64; N]], x: f64) -> Option<[f64; N]> {
+    let i = rows.iter().position(|row| row[0] >= x)?;
+    if rows[i][0] == x {
+        return Some(rows[i]);
+    }
+    let (a, b) = (rows.get(i.checked_sub(1)?)?, rows[i]);
+    let t = (x - a[0]) / (b[0] - a[0]);
+    let mut row = [0.; N];
+    for (j, value) in row.iter_mut().enumerate() {
+        *value = a[j] + t * (b[j] - a[j]);
+    }
+    Some(row)
+}
+
+/// Forward and aft CG limits (fraction of MAC) as a function of the weight.
+pub struct Envelope {
+    rows: Vec<[f64; 3]>, // weight (lb), forward limit, aft limit
+}
+
+impl Envelope {
+    /// Forward and aft limits, none outside the weights of the envelope.
+    pub fn limits(&self, weight: f64) -> Option<(f64, f64)> {
+        interpolate(&self.rows, weight).map(|row| (row[1], row[2]))
+    }
+}
+
+/// Reads the CG envelope, one row per weight with columns weight (lb) and
+/// the forward and aft limits (fraction of MAC), linear in between.
+pub fn read_envelope(path: &str) -> Result<Envelope, Box<dyn Error>> {
+    Ok(Envelope { rows: read_table(path)? })
+}
+
+/// Deadload or passengers of one zone or hold.
+pub struct Line {
+    name: String,
+    weight: f64, // lb
+    x: f64, // ft
+    index: f64, // change of index
+}
+
+impl Line {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn weight(&self) -> f64 {
+        self.weight
+    }
+
+    pub fn index(&self) -> f64 {
+        self.index
+    }
+}
+
+/// Arm of the fuel (ft) as a function of its weight, following the order in
+/// which the tanks are filled.
+pub struct FuelArms {
+    rows: Vec<[f64; 2]>, // fuel weight (lb), arm (ft)
+}
+
+impl FuelArms {
+    /// Arm, none outside the fuel weights of the table.
+    pub fn arm(&self, weight: f64) -> Option<f64> {
+        interpolate(&self.rows, weight).map(|row| row[1])
+    }
+}
+
+/// Reads the fuel arms, one row per fuel weight with columns weight (lb)
+/// and arm (ft), none when the file does not exist.
+pub fn read_fuel_arms(path: &str) -> Result<Option<FuelArms>, Box<dyn Error>> {
+    if !Path::new(path).exists() {
+        return Ok(None);
+    }
+    Ok(Some(FuelArms { rows: read_table(path)? }))
+}
+
+/// Weight and CG of the aircraft at one stage of the flight.
+pub struct Total {
+    name: &'static str,
+    weight: f64, // lb
+    x: f64, // ft
+    cg_mac: f64, // fraction of MAC
+    index: f64,
+    max_weight: Option<f64>, // structural limit (lb)
+    cg_limits: Option<(f64, f64)>, // forward and aft at this weight, none outside the envelope (fraction of MAC)
+}
+
+impl Total {
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn weight(&self) -> f64 {
+        self.weight
+    }
+
+    pub fn cg_mac(&self) -> f64 {
+        self.cg_mac
+    }
+
+    pub fn index(&self) -> f64 {
+        self.index
+    }
+
+    pub fn cg_limits(&self) -> Option<(f64, f64)> {
+        self.cg_limits
+    }
+
+    /// Forward and aft limits in %MAC as text, "-" outside the envelope.
+    fn limits_text(&self) -> (String, String) {
+        self.cg_limits.map_or(("-".to_string(), "-".to_string()), |(fwd, aft)| {
+            (format!("{:.1}", 100. * fwd), format!("{:.1}", 100. * aft))
+        })
+    }
+}
+
+/// Load and trim sheet: dry operating, zero fuel, take-off and landing
+/// weights with their CG in %MAC and in index units,
+/// I = W (x - x_ref) / C + K, checked against the weight and CG limits.
+///
+/// The cabin zones are given by n_zones and zone_<n>_first_row,
+/// zone_<n>_last_row, rows of the seat map from 1 at the front, the holds by
+/// n_holds, x_hold_<n> (ft) and optionally max_hold_<n> (lb). The index uses
+/// index_ref (ft, 25% MAC unless given), index_scale (10000 unless given)
+/// and index_constant (50 unless given). The limits are w_mzf, w_mtow (w_dg
+/// unless given) and w_mlw (w_l unless given), and the CG envelope. Without
+/// a fuel arm table, the fuel CG is the one of the fuel parameters whatever
+/// the fuel weight, which the sheet notes.
+pub struct LoadSheet {
+    lines: Vec<Line>,
+    totals: Vec<Total>, // DOW, ZFW, TOW, LAW
+    passengers: f64,
+    underload: Option<f64>, // weight still allowed by the most limiting of the maximum weights (lb)
+    notes: Vec<String>,
+    violations: Vec<String>,
+}
+
+fn moment(items: &[Item]) -> (f64, f64) {
+    let weight: f64 = items.iter().map(|item| item.weight().get_val()).sum();
+    let moment: f64 = items.iter().map(|item| *item.pos_times_weight().x()).sum();
+    (weight, moment)
+}
+
+impl LoadSheet {
+    pub fn new(
+        params: &Params,
+        load: &Params,
+        envelope: &Envelope,
+        fuel_arms: Option<&FuelArms>,
+        evaluation: &Evaluation,
+    ) -> Result<Self, Box<dyn Error>> {
+        let x_ref = params.get("index_ref")
+            .copied()
+            .unwrap_or_else(|| evaluation.x_le_mac() + 0.25 * evaluation.stability().c_bar());
+        let index_scale = params.get("index_scale").copied().unwrap_or(10000.);
+        let index_constant = params.get("index_constant").copied().unwrap_or(50.);
+        let index = |weight: f64, moment: f64| (moment - weight * x_ref) / index_scale;
+        let w_passenger = params.get("w_passenger").copied().unwrap_or_else(passenger_weight);
+        let mut violations = vec![];
+
+        let mut lines = vec![];
+        let mut payload: Vec<Item> = vec![];
+        let mut passengers = 0.;
+        let n_zones = params.get("n_zones").copied().unwrap_or(0.) as usize;
+        for n in 1..=n_zones {
+            let first = *params.get(&format!("zone_{}_first_row", n)).ok_or_else(|| format!("missing zone_{}_first_row", n))?;
+            let last = *params.get(&format!("zone_{}_last_row", n)).ok_or_else(|| format!("missing zone_{}_last_row", n))?;
+            if first < 1. || first > last || last > NUM_ROWS {
+                return Err(format!("zone {}: rows {} to {} not within the {} rows of the cabin", n, first, last, NUM_ROWS).into());
+            }
+            let pax = load.get(&format!("pax_zone_{}", n)).copied().unwrap_or(0.);
+            let seats = (last - first + 1.) * PASSENGERS_ABREAST;
+            if pax > seats {
+                violations.push(format!("zone {}: {} passengers for {} seats", n, pax, seats));
+            }
+            passengers += pax;
+            // an empty zone has no CG
+            let zone = (pax > 0.).then(|| Passengers::new(pax, w_passenger, LoadCase::Rows { first, last }).item());
+            let (weight, x) = zone.as_ref().map_or((0., 0.), |item| (item.weight().get_val(), *item.cg().x()));
+            payload.extend(zone);
+            lines.push(Line {
+                name: format!("zone {} (rows {}-{}), {} pax", n, first, last, pax),
+                weight,
+                x,
+                index: index(weight, weight * x),
+            });
+        }
+        let n_holds = params.get("n_holds").copied().unwrap_or(0.) as usize;
+        for n in 1..=n_holds {
+            let x = *params.get(&format!("x_hold_{}", n)).ok_or_else(|| format!("missing x_hold_{}", n))?;
+            let bags = load.get(&format!("bags_hold_{}", n)).copied().unwrap_or(0.);
+            let cargo = load.get(&format!("cargo_hold_{}", n)).copied().unwrap_or(0.);
+            if let Some(max) = params.get(&format!("max_hold_{}", n)) {
+                if bags + cargo > *max {
+                    violations.push(format!("hold {}: {} lb above the limit of {} lb", n, bags + cargo, max));
+                }
+            }
+            for (kind, weight) in [("baggage", bags), ("cargo", cargo)] {
+                payload.push(Payload::new(weight, Point::new(x, 0., 0.)).item());
+                lines.push(Line {
+                    name: format!("hold {} {}", n, kind),
+                    weight,
+                    x,
+                    index: index(weight, weight * x),
+                });
+            }
+        }
+
+        let fuel_takeoff = *load.get("fuel_takeoff").ok_or("missing fuel_takeoff")?;
+        let fuel_trip = *load.get("fuel_trip").ok_or("missing fuel_trip")?;
+        if fuel_trip > fuel_takeoff {
+            violations.push(format!("trip fuel {} lb above the take-off fuel {} lb", fuel_trip, fuel_takeoff));
+        }
+        let fuel_cg = fuel(params).item().cg();
+        let mut notes = vec![];
+        if fuel_arms.is_none() {
+            notes.push(format!("fuel CG at {:.2} ft whatever the fuel weight, no fuel arm table given", fuel_cg.x()));
+        }
+        let fuel_item = |weight: f64| -> Result<Item, String> {
+            let x = match fuel_arms {
+                Some(arms) => arms.arm(weight).ok_or_else(|| format!("fuel {} lb outside the fuel arm table", weight))?,
+                None => *fuel_cg.x(),
+            };
+            Ok(Fuel::new(weight, Point::new(x, *fuel_cg.y(), *fuel_cg.z())).item())
+        };
+
+        let dry_operating = evaluation.condition("operating empty").items().to_vec();
+        let mut zero_fuel = dry_operating.clone();
+        zero_fuel.extend(payload);
+        let mut takeoff = zero_fuel.clone();
+        takeoff.push(fuel_item(fuel_takeoff)?);
+        let mut landing = zero_fuel.clone();
+        landing.push(fuel_item(fuel_takeoff - fuel_trip)?);
+
+        let w_mtow = params.get("w_mtow").copied().unwrap_or_else(|| evaluation.w_dg());
+        let w_mlw = params.get("w_mlw").or_else(|| params.get("w_l")).copied();
+        let totals: Vec<Total> = [
+            ("DOW", &dry_operating, None),
+            ("ZFW", &zero_fuel, params.get("w_mzf").copied()),
+            ("TOW", &takeoff, Some(w_mtow)),
+            ("LAW", &landing, w_mlw),
+        ]
+        .into_iter()
+        .map(|(name, items, max_weight)| {
+            let (weight, m) = moment(items);
+            let x = m / weight;
+            Total {
+                name,
+                weight,
+                x,
+                cg_mac: evaluation.cg_mac(x),
+                index: index(weight, m) + index_constant,
+                max_weight,
+                cg_limits: envelope.limits(weight),
+            }
+        })
+        .collect();
+
+        for total in &totals {
+            if let Some(max) = total.max_weight {
+                if total.weight > max {
+                    violations.push(format!("{} {:.0} lb above the maximum of {:.0} lb", total.name, total.weight, max));
+                }
+            }
+            match total.cg_limits {
+                Some((fwd, aft)) if total.cg_mac < fwd || total.cg_mac > aft => violations.push(format!(
+                    "{} CG {:.1} %MAC outside {:.1} to {:.1} %MAC",
+                    total.name, 100. * total.cg_mac, 100. * fwd, 100. * aft,
+                )),
+                Some(_) => {}
+                None => violations.push(format!("{} {:.0} lb outside the weights of the CG envelope", total.name, total.weight)),
+            }
+        }
+        let underload = totals.iter()
+            .filter_map(|total| total.max_weight.map(|max| max - total.weight))
+            .reduce(f64::min);
+
+        Ok(Self { lines, totals, passengers, underload, notes, violations })
+    }
+
+    pub fn lines(&self) -> &[Line] {
+        &self.lines
+    }
+
+    pub fn totals(&self) -> &[Total] {
+        &self.totals
+    }
+
+    pub fn violations(&self) -> &[String] {
+        &self.violations
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::from("LOAD AND TRIM SHEET\n\n");
+        text += &format!("{:<36} {:>10} {:>9} {:>9}\n", "LOAD", "WEIGHT LB", "ARM FT", "INDEX");
+        for line in &self.lines {
+            text += &format!("{:<36} {:>10.0} {:>9.2} {:>+9.2}\n", line.name, line.weight, line.x, line.index);
+        }
+        text += &format!("{:<36} {:>10.0}\n\n", "total passengers", self.passengers);
+        text += &format!(
+            "{:<8} {:>10} {:>10} {:>9} {:>9} {:>9} {:>9} {:>9}\n",
+            "", "WEIGHT LB", "MAX LB", "ARM FT", "%MAC", "FWD %MAC", "AFT %MAC", "INDEX",
+        );
+        for total in &self.totals {
+            let (fwd, aft) = total.limits_text();
+            text += &format!(
+                "{:<8} {:>10.0} {:>10} {:>9.2} {:>9.1} {:>9} {:>9} {:>9.2}\n",
+                total.name,
+                total.weight,
+                total.max_weight.map_or("-".to_string(), |max| format!("{:.0}", max)),
+                total.x,
+                100. * total.cg_mac,
+                fwd,
+                aft,
+                total.index,
+            );
+        }
+        text += "\n";
+        if let Some(underload) = self.underload {
+            text += &format!("underload {:.0} lb\n", underload);
+        }
+        if self.violations.is_empty() {
+            text += "all limits met\n";
+        }
+        for violation in &self.violations {
+            text += &format!("LIMIT EXCEEDED: {}\n", violation);
+        }
+        for note in &self.notes {
+            text += &format!("NOTE: {}\n", note);
+        }
+        text
+    }
+
+    /// Standalone page, laid out for printing to PDF from a browser.
+    pub fn to_html(&self) -> String {
+        let mut html = String::from(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Load and trim sheet</title>\n<style>\n\
+             @page { size: A4; margin: 15mm; }\n\
+             body { font-family: monospace; max-width: 180mm; margin: auto; }\n\
+             table { border-collapse: collapse; width: 100%; margin: 1em 0; }\n\
+             th, td { border: 1px solid #000; padding: 0.2em 0.5em; }\n\
+             td:not(:first-child) { text-align: right; }\n\
+             .exceeded { color: #c00; font-weight: bold; }\n\
+             </style>\n</head>\n<body>\n<h1>Load and trim sheet</h1>\n",
+        );
+        html += "<table>\n<tr><th>Load</th><th>Weight (lb)</th><th>Arm (ft)</th><th>Index</th></tr>\n";
+        for line in &self.lines {
+            html += &format!(
+                "<tr><td>{}</td><td>{:.0}</td><td>{:.2}</td><td>{:+.2}</td></tr>\n",
+                escape(&line.name), line.weight, line.x, line.index,
+            );
+        }
+        html += &format!("<tr><td>total passengers</td><td>{:.0}</td><td></td><td></td></tr>\n</table>\n", self.passengers);
+        html += "<table>\n<tr><th></th><th>Weight (lb)</th><th>Max (lb)</th><th>Arm (ft)</th><th>%MAC</th>\
+                 <th>Fwd limit (%MAC)</th><th>Aft limit (%MAC)</th><th>Index</th></tr>\n";
+        for total in &self.totals {
+            let (fwd, aft) = total.limits_text();
+            html += &format!(
+                "<tr><td>{}</td><td>{:.0}</td><td>{}</td><td>{:.2}</td><td>{:.1}</td><td>{}</td><td>{}</td><td>{:.2}</td></tr>\n",
+                total.name,
+                total.weight,
+                total.max_weight.map_or("-".to_string(), |max| format!("{:.0}", max)),
+                total.x,
+                100. * total.cg_mac,
+                fwd,
+                aft,
+                total.index,
+            );
+        }
+        html += "</table>\n";
+        if let Some(underload) = self.underload {
+            html += &format!("<p>Underload {:.0} lb</p>\n", underload);
+        }
+        if self.violations.is_empty() {
+            html += "<p>All limits met</p>\n";
+        }
+        for violation in &self.violations {
+            html += &format!("<p class=\"exceeded\">Limit exceeded: {}</p>\n", escape(violation));
+        }
+        for note in &self.notes {
+            html += &format!("<p>Note: {}</p>\n", escape(note));
+        }
+        html += "</body>\n</html>\n";
+        html
+    }
+}
+
+/// Writes the sheet as text and as HTML.
+pub fn write_load_sheet(text_path: &str, html_path: &str, sheet: &LoadSheet) -> Result<(), Box<dyn Error>> {
+    fs::write(text_path, sheet.to_text())?;
+    fs::write(html_path, sheet.to_html())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample_params;
+
+    fn sample_sheet(rows: (f64, f64)) -> Result<(LoadSheet, Evaluation), Box<dyn Error>> {
+        let mut params = sample_params();
+        for (symbol, value) in [
+            ("n_zones", 1.), ("zone_1_first_row", rows.0), ("zone_1_last_row", rows.1),
+            ("n_holds", 1.), ("x_hold_1", 25.), ("w_passenger", 200.),
+            ("index_ref", 30.), ("index_scale", 1000.), ("index_constant", 40.),
+        ] {
+            params.insert(symbol.to_string(), value);
+        }
+        let load = Params::from([
+            ("pax_zone_1".to_string(), 12.),
+            ("cargo_hold_1".to_string(), 1000.),
+            ("fuel_takeoff".to_string(), 8000.),
+            ("fuel_trip".to_string(), 5000.),
+        ]);
+        let envelope = Envelope { rows: vec![[0., -1., 1.], [1e6, -1., 1.]] };
+        let evaluation = Evaluation::new(&params, None);
+        Ok((LoadSheet::new(&params, &load, &envelope, None, &evaluation)?, evaluation))
+    }
+
+    #[test]
+    fn totals_match_hand_computed_sheet() {
+        let (sheet, evaluation) = sample_sheet((1., 2.)).unwrap();
+        let close = |a: f64, b: f64| (a - b).abs() <= 1e-9 * b.abs().max(1.);
+        // 12 passengers of 200 lb in rows 1 and 2, one row length aft of the first seats
+        let x_zone = (39.37 + 6. + 53. / 12.) / 12. + (15. * 32. + 18.) / 12. / 15.;
+        let (w_dow, x_dow) = (evaluation.oew(), *evaluation.condition("operating empty").cg().x());
+        let (w_zfw, m_zfw) = (w_dow + 2400. + 1000., w_dow * x_dow + 2400. * x_zone + 1000. * 25.);
+        let expected = [
+            ("DOW", w_dow, w_dow * x_dow),
+            ("ZFW", w_zfw, m_zfw),
+            ("TOW", w_zfw + 8000., m_zfw + 8000. * 40.),
+            ("LAW", w_zfw + 3000., m_zfw + 3000. * 40.),
+        ];
+        assert_eq!(sheet.totals().len(), expected.len());
+        for (total, (name, weight, moment)) in sheet.totals().iter().zip(expected) {
+            let cg_mac = (moment / weight - evaluation.x_le_mac()) / evaluation.stability().c_bar();
+            assert_eq!(total.name(), name);
+            assert!(close(total.weight(), weight), "{} weight {} != {}", name, total.weight(), weight);
+            assert!(close(total.cg_mac(), cg_mac), "{} CG {} != {}", name, total.cg_mac(), cg_mac);
+            let index = (moment - weight * 30.) / 1000. + 40.;
+            assert!(close(total.index(), index), "{} index {} != {}", name, total.index(), index);
+        }
+        assert!(close(sheet.lines()[0].weight(), 2400.));
+    }
+
+    #[test]
+    fn zone_rows_within_the_cabin() {
+        assert!(sample_sheet((1., 15.)).is_ok());
+        assert!(sample_sheet((3., 2.)).is_err());
+        assert!(sample_sheet((0., 2.)).is_err());
+        assert!(sample_sheet((14., 16.)).is_err());
+    }
+
+    #[test]
+    fn envelope_limits_are_interpolated_within_its_weights() {
+        let envelope = Envelope { rows: vec![[30000., 0.10, 0.38], [40000., 0.14, 0.34], [50000., 0.20, 0.30]] };
+        assert_eq!(envelope.limits(30000.), Some((0.10, 0.38)));
+        let (fwd, aft) = envelope.limits(45000.).unwrap();
+        assert!((fwd - 0.17).abs() < 1e-12 && (aft - 0.32).abs() < 1e-12);
+        assert_eq!(envelope.limits(50000.), Some((0.20, 0.30)));
+        assert_eq!(envelope.limits(29999.), None);
+        assert_eq!(envelope.limits(50001.), None);
+    }
+}
@@ -0,0 +1,56 @@
+use crate::components::Item;
+use crate::inertia::Inertia;
+use crate::utils::point::Point;
+
+/// Empty weight items together with one combination of varying loads.
+pub struct LoadingCondition {
+    name: String,
+    items: Vec<Item>,
+}
+
+impl LoadingCondition {
+    pub fn new(name: &str, items: Vec<Item>) -> Self {
+        Self {
+            name: name.to_string(),
+            items,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn items(&self) -> &[Item] {
+        &self.items
+    }
+
+    /// lb
+    pub fn weight(&self) -> f64 {
+        self.items.iter().map(|item| item.weight().get_val()).sum()
+    }
+
+    pub fn moment(&self) -> Point<f64> {
+        self.items.iter()
+            .map(|item| item.pos_times_weight())
+            .fold(Point::new(0., 0., 0.), |a, b| a + b)
+    }
+
+    pub fn cg(&self) -> Point<f64> {
+        self.moment() / self.weight()
+    }
+
+    pub fn inertia(&self) -> Inertia {
+        Inertia::new(&self.items)
+    }
+}
+
+/// Returns the most forward and the most aft of the loading conditions.
+pub fn cg_limits(conditions: &[LoadingCondition]) -> (&LoadingCondition, &LoadingCondition) {
+    let fwd = conditions.iter()
+        .min_by(|a, b| a.cg().x().total_cmp(b.cg().x()))
+        .expect("no loading condition");
+    let aft = conditions.iter()
+        .max_by(|a, b| a.cg().x().total_cmp(b.cg().x()))
+        .expect("no loading condition");
+    (fwd, aft)
+}
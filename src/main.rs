@@ -1,69 +1,497 @@
 pub mod components;
 pub mod utils;
 pub mod stability;
+pub mod aerodynamics;
+pub mod engine_out;
+pub mod loading;
+pub mod ground_handling;
+pub mod ground_loads;
+pub mod inertia;
+pub mod lateral;
+pub mod class_one;
+pub mod contingency;
+pub mod calibration;
+pub mod evaluation;
+pub mod sensitivity;
+pub mod monte_carlo;
+pub mod sweep;
+pub mod optimisation;
+pub mod export;
+pub mod plot;
+pub mod report;
+pub mod layout;
+pub mod load_sheet;
 
 use std::collections::HashMap;
 use std::error::Error;
 use csv::Reader;
-use crate::components::wings::Wings;
-use crate::components::tailplane::Tailplane;
-use crate::components::fuselage::Fuselage;
-use crate::utils::*;
-use utils::point::Point;
-use crate::components::varying::*;
+use crate::components::Aircraft;
+use crate::components::method::Method;
+use crate::engine_out::EngineOut;
+use crate::aerodynamics::fuselage_moment::{FuselageMoment, FuselageStation};
+use crate::loading::cg_limits;
+use crate::ground_handling::GearGeometry;
+use crate::ground_loads::{GroundLoads, write_load_table};
+use crate::lateral::LateralBalance;
+use crate::class_one::{ClassOne, write_seeds};
+use crate::contingency::{Contingency, append_history};
+use crate::calibration::{read_reference_aircraft, calibrate, write_factors};
+use crate::evaluation::{Evaluation, check_codes};
+use crate::sensitivity::{Ranking, sensitivities, write_sensitivities};
+use crate::monte_carlo::{read_uncertainties, run, Statistics, write_samples};
+use crate::sweep::{Design, read_factors, evaluate_points, write_outcomes};
+use crate::optimisation::{Output, Problem, read_variables, read_constraints, nelder_mead, sqp, write_solution};
+use crate::export::{document, write_json, write_csv};
+use crate::report::{Report, write_report};
+use crate::plot::{SCISSOR_PLOT_INPUTS, loading_diagram, scissor_plot, write_svg};
+use crate::layout::{ThreeView, write_three_view};
+use crate::load_sheet::{LoadSheet, read_load, read_envelope, read_fuel_arms, write_load_sheet};
 
 const WEIGHTS_FILE_PATH: &str = "./data/weights.csv"; // relative path to parameters file
 const BALANCE_FILE_PATH: &str = "./data/balance2.csv";
+const FUSELAGE_STATIONS_FILE_PATH: &str = "./data/fuselage_stations.csv";
+const GROUND_LOADS_FILE_PATH: &str = "./data/ground_loads.csv";
+const CLASS_ONE_SEEDS_FILE_PATH: &str = "./data/class_one_seeds.csv";
+const CONTINGENCY_HISTORY_FILE_PATH: &str = "./data/contingency_history.csv";
+const REFERENCE_AIRCRAFT_FILE_PATH: &str = "./data/reference_aircraft.csv";
+const CALIBRATION_FILE_PATH: &str = "./data/calibration.csv";
+const SENSITIVITY_FILE_PATH: &str = "./data/sensitivity.csv";
+const UNCERTAINTIES_FILE_PATH: &str = "./data/uncertainties.csv";
+const MONTE_CARLO_FILE_PATH: &str = "./data/monte_carlo.csv";
+const SWEEP_FILE_PATH: &str = "./data/sweep.csv";
+const SWEEP_RESULTS_FILE_PATH: &str = "./data/sweep_results.csv";
+const DESIGN_VARIABLES_FILE_PATH: &str = "./data/design_variables.csv";
+const CONSTRAINTS_FILE_PATH: &str = "./data/constraints.csv";
+const OPTIMUM_FILE_PATH: &str = "./data/optimum.csv";
+const OPTIMISATION_ITERATIONS: usize = 200;
+const RESULTS_JSON_FILE_PATH: &str = "./data/results.json";
+const RESULTS_CSV_FILE_PATH: &str = "./data/results.csv";
+const REPORT_DIR_PATH: &str = "./data/report";
+const LOADING_DIAGRAM_FILE_PATH: &str = "./data/loading_diagram.svg";
+const SCISSOR_PLOT_FILE_PATH: &str = "./data/scissor_plot.svg";
+const THREE_VIEW_FILE_PATH: &str = "./data/three_view.svg";
+const LOAD_FILE_PATH: &str = "./data/load.csv";
+const CG_ENVELOPE_FILE_PATH: &str = "./data/cg_envelope.csv";
+const FUEL_ARMS_FILE_PATH: &str = "./data/fuel_arms.csv";
+const LOAD_SHEET_TEXT_FILE_PATH: &str = "./data/load_sheet.txt";
+const LOAD_SHEET_HTML_FILE_PATH: &str = "./data/load_sheet.html";
 type Params = HashMap<String, f64>;
+type Sources = HashMap<String, String>; // file each parameter was read from
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let params: Params = read_params_files()?;
+    let (mut params, sources) = read_params_files()?;
+    check_codes(&params)?;
+    match std::env::args().nth(1).as_deref() {
+        Some("class-one") => return class_one(&params),
+        Some("calibrate") => return calibrate_estimators(&params),
+        _ => {}
+    }
+    // the class I estimate stands in for the class II parameters not yet known
+    if params.contains_key("class_one_type") {
+        ClassOne::new(&params)?.seed(&mut params);
+    }
+
+    let stations = if params.get("fuselage_moment_method").copied().unwrap_or(0.) == 1. {
+        Some(read_fuselage_stations()?)
+    } else {
+        None
+    };
+    let args: Vec<String> = std::env::args().collect();
+    let arg = |i: usize| args.get(i).map(String::as_str);
+    match arg(1) {
+        Some("sensitivity") => {
+            let ranking = Ranking::from_arg(arg(2).unwrap_or("oew"))?;
+            return sensitivity(&params, stations.as_deref(), ranking);
+        }
+        // monte-carlo [samples] [seed] [threads]
+        Some("monte-carlo") => {
+            let samples = arg(2).map_or(Ok(1000), str::parse)?;
+            // the standard deviation needs two
+            if samples < 2 {
+                return Err(format!("{} samples, expected at least 2", samples).into());
+            }
+            let seed = arg(3).map_or(Ok(1), str::parse)?;
+            let threads = arg(4).map_or(Ok(1), str::parse)?;
+            return monte_carlo(&params, stations.as_deref(), samples, seed, threads);
+        }
+        // sweep [full|lhs|oat] [samples] [seed]
+        Some("sweep") => {
+            let design = match arg(2).unwrap_or("full") {
+                "full" => Design::FullFactorial,
+                "lhs" => Design::LatinHypercube {
+                    samples: arg(3).map_or(Ok(100), str::parse)?,
+                    seed: arg(4).map_or(Ok(1), str::parse)?,
+                },
+                "oat" => Design::OneAtATime,
+                other => return Err(format!("unknown design {}, expected full, lhs or oat", other).into()),
+            };
+            let factors = read_factors(SWEEP_FILE_PATH, &params)?;
+            let points = design.points(&factors, &params);
+            let outcomes = evaluate_points(&params, stations.as_deref(), &factors, points);
+            println!("{} points written to {}", outcomes.len(), SWEEP_RESULTS_FILE_PATH);
+            write_outcomes(SWEEP_RESULTS_FILE_PATH, &factors, &outcomes)?;
+            return Ok(());
+        }
+        // optimise [nm|sqp] [objective]
+        Some("optimise") => {
+            let objective = Output::from_name(arg(3).unwrap_or("oew"))?;
+            let problem = Problem::new(
+                &params,
+                stations.as_deref(),
+                read_variables(DESIGN_VARIABLES_FILE_PATH, &params)?,
+                objective,
+                read_constraints(CONSTRAINTS_FILE_PATH)?,
+            )?;
+            let solution = match arg(2).unwrap_or("sqp") {
+                "nm" => nelder_mead(&problem, OPTIMISATION_ITERATIONS),
+                "sqp" => sqp(&problem, OPTIMISATION_ITERATIONS),
+                other => return Err(format!("unknown optimiser {}, expected nm or sqp", other).into()),
+            };
+            for (symbol, value) in solution.values() {
+                println!("{}: {} (from {})", symbol, value, params[symbol]);
+            }
+            println!(
+                "objective: {} {}, {} ({} iterations, {} evaluations)",
+                solution.objective(),
+                objective.unit(),
+                if solution.feasible() { "feasible" } else { "infeasible" },
+                solution.iterations(),
+                solution.evaluations(),
+            );
+            write_solution(OPTIMUM_FILE_PATH, &solution)?;
+            return Ok(());
+        }
+        // appends the contingency burn down history, once per design iteration
+        Some("record-contingency") => {
+            let evaluation = Evaluation::new(&params, stations.as_deref());
+            let params = evaluation.calibrated(&params);
+            let lines = Contingency::new(&params).lines(&evaluation.aircraft().items(evaluation.w_dg()));
+            if append_history(CONTINGENCY_HISTORY_FILE_PATH, &lines)? {
+                println!("contingency recorded in {}", CONTINGENCY_HISTORY_FILE_PATH);
+            } else {
+                println!("contingency unchanged since the last record");
+            }
+            return Ok(());
+        }
+        Some("report") => {
+            let evaluation = Evaluation::new(&params, stations.as_deref());
+            let report = Report::new(&evaluation.calibrated(&params), &sources, &evaluation);
+            write_report(REPORT_DIR_PATH, &report)?;
+            println!("report written to {}", REPORT_DIR_PATH);
+            return Ok(());
+        }
+        Some("load-sheet") => {
+            let evaluation = Evaluation::new(&params, stations.as_deref());
+            let sheet = LoadSheet::new(
+                &params,
+                &read_load(LOAD_FILE_PATH)?,
+                &read_envelope(CG_ENVELOPE_FILE_PATH)?,
+                read_fuel_arms(FUEL_ARMS_FILE_PATH)?.as_ref(),
+                &evaluation,
+            )?;
+            print!("{}", sheet.to_text());
+            write_load_sheet(LOAD_SHEET_TEXT_FILE_PATH, LOAD_SHEET_HTML_FILE_PATH, &sheet)?;
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    let evaluation = Evaluation::new(&params, stations.as_deref());
+    let aircraft = evaluation.aircraft();
+    let w_dg = evaluation.w_dg();
+    let conditions = evaluation.conditions();
+    for (symbol, k) in evaluation.calibrations() {
+        println!("{}: {}", symbol, k);
+    }
+    let params = evaluation.calibrated(&params);
+
+    let total_weight = conditions[0].weight();
+    let cg = conditions[0].cg();
+    for (group, items) in aircraft.groups(w_dg) {
+        let weight: f64 = items.iter().map(|item| item.weight().get_val()).sum();
+        println!("{}: {} lb", group, weight);
+        for item in items {
+            let mut notes = vec![];
+            if let Some(method) = item.method() {
+                notes.push(method.name().to_string());
+            }
+            if item.technology() != 1. {
+                notes.push(format!("technology x{}", item.technology()));
+            }
+            if let Some(k) = item.calibration() {
+                notes.push(format!("calibrated x{}", k));
+            }
+            if let Some(estimate) = item.estimate() {
+                notes.push(format!("estimate {} lb", estimate.get_val()));
+            }
+            if notes.is_empty() {
+                println!("    {}: {} lb", item.name(), item.weight().get_val());
+            } else {
+                println!("    {}: {} lb ({})", item.name(), item.weight().get_val(), notes.join(", "));
+            }
+        }
+    }
+    let contingency_lines = Contingency::new(&params).lines(&aircraft.items(w_dg));
+    let mut chapters: Vec<Option<u32>> = contingency_lines.iter().map(|line| line.ata()).collect();
+    chapters.sort();
+    chapters.dedup();
+    for ata in chapters {
+        let lines: Vec<_> = contingency_lines.iter().filter(|line| line.ata() == ata).collect();
+        println!(
+            "ATA {}: {} lb + {} lb contingency",
+            ata.map_or("-".to_string(), |ata| ata.to_string()),
+            lines.iter().map(|line| line.weight()).sum::<f64>(),
+            lines.iter().map(|line| line.contingency()).sum::<f64>(),
+        );
+        for line in lines {
+            println!("    {} ({}): {} lb", line.name(), line.maturity().name(), line.contingency());
+        }
+    }
+    let total_contingency: f64 = contingency_lines.iter().map(|line| line.contingency()).sum();
+    println!("computed empty weight: {} lb", total_weight - total_contingency);
+    println!("contingency: {} lb", total_contingency);
+    println!("total weight: {} lb", total_weight);
+    // spread of the empty weight when every component uses the same method,
+    // for the methods whose inputs are all given
+    let (k_y, k_z) = evaluation.radii_of_gyration();
+    for method in Method::ALL {
+        let missing: Vec<&str> = method.inputs().iter()
+            .filter(|symbol| !params.contains_key(**symbol))
+            .copied()
+            .collect();
+        if !missing.is_empty() {
+            println!("empty weight ({}): skipped, missing {}", method.name(), missing.join(", "));
+            continue;
+        }
+        let mut alternative = Aircraft::new(&params);
+        alternative.set_method(method);
+        alternative.set_i_y(evaluation.i_y());
+        alternative.set_radii_of_gyration(k_y, k_z);
+        println!("empty weight ({}): {} lb", method.name(), alternative.weight(w_dg).get_val());
+    }
+    println!("cg: {}", cg.x());
+    let lateral_balance = LateralBalance::new(&params);
+    for condition in conditions {
+        let cg = condition.cg();
+        println!(
+            "{}: {} lb, cg x {} y {} z {}, rolling moment {} lb.ft{}",
+            condition.name(),
+            condition.weight(),
+            cg.x(),
+            cg.y(),
+            cg.z(),
+            lateral_balance.rolling_moment(condition),
+            if lateral_balance.within_limit(condition) { "" } else { " (above lateral imbalance limit)" },
+        );
+        let inertia = condition.inertia();
+        println!(
+            "{}: Ixx {} Iyy {} Izz {} Ixz {} lb.ft2",
+            condition.name(),
+            inertia.i_xx(),
+            inertia.i_yy(),
+            inertia.i_zz(),
+            inertia.i_xz(),
+        );
+    }
 
-    // empty weight
-    let wings = Wings::new(&params);    
-    let fuselage = Fuselage::new(&params);
-    let tailplane = Tailplane::new(&params);
+    let stability = evaluation.stability();
+    if stations.is_some() {
+        println!(
+            "fuselage cm_alpha: {} /rad (Gilruth {} /rad)",
+            stability.c_maf(),
+            stability.fuselage_cm_alpha(&FuselageMoment::gilruth(&params)),
+        );
+    }
+    println!("cg: {} %MAC", 100. * evaluation.cg_mac(*cg.x()));
+    println!("neutral point: {}", stability.x_np());
+    println!("static margin: {} %MAC", 100. * stability.static_margin(*cg.x()));
 
-    // varying weights
-    let pilots = Pilots::new(2., 85.);
-    let crew = Crew::new(3., 85.);
-    let passengers = Passengers::new(90., 100., LoadCase::Center);
+    let engine_out = EngineOut::new(&params);
+    println!(
+        "vmca: {} ft/s (limit {} ft/s, fin {})",
+        engine_out.vmca(),
+        engine_out.vmca_limit(),
+        if engine_out.meets_vmca_limit() { "ok" } else { "too small" },
+    );
+    println!("required fin area: {} ft2", engine_out.s_vt_required());
 
-    let w_dg = kg_to_lb(38249.);
+    let (fwd, aft) = cg_limits(conditions);
+    println!("forward cg: {} ({})", fwd.cg().x(), fwd.name());
+    println!("aft cg: {} ({})", aft.cg().x(), aft.name());
+    let gear = GearGeometry::new(&params);
+    println!(
+        "nose gear load: {} to {}",
+        gear.nose_load_fraction(aft.cg()),
+        gear.nose_load_fraction(fwd.cg()),
+    );
+    println!("tip-back angle: {} deg", gear.tip_back_angle(aft.cg()));
+    println!("tail strike angle: {} deg", gear.tail_strike_angle());
+    println!("turnover angle: {} deg", gear.turnover_angle(fwd.cg()));
+    for violation in gear.violations(fwd.cg(), aft.cg()) {
+        println!("gear placement: {}", violation);
+    }
 
-    let total_weight = wings.weight(w_dg) +
-        fuselage.weight(w_dg) +
-        tailplane.weight(w_dg);
+    // gear loads at MTOW over the CG range
+    let ground_loads = GroundLoads::new(&params);
+    let load_cases: Vec<_> = conditions.iter()
+        .map(|condition| ground_loads.load_case(&gear, condition.name(), w_dg, condition.cg()))
+        .collect();
+    let max_nose_braking = load_cases.iter().map(|l| l.nose_braking()).fold(0., f64::max);
+    let max_main_static = load_cases.iter().map(|l| l.main_static()).fold(0., f64::max);
+    println!("max main gear static load: {} lb per leg", max_main_static);
+    println!("max nose gear braking load: {} lb", max_nose_braking);
+    write_load_table(GROUND_LOADS_FILE_PATH, &load_cases)?;
 
-    let cg = (
-            wings.pos_times_weight(w_dg, Point::new(28.2, 0., 0.)) +
-            tailplane.pos_times_weight(w_dg) +
-            fuselage.pos_times_weight(w_dg)
-        ) / total_weight.get_val();
-    
+    let results = document(&params, &evaluation);
+    write_json(RESULTS_JSON_FILE_PATH, &results)?;
+    write_csv(RESULTS_CSV_FILE_PATH, &results)?;
+    write_svg(LOADING_DIAGRAM_FILE_PATH, &loading_diagram(&params, &evaluation))?;
+    // the controllability limit needs the landing aerodynamic coefficients
+    let missing: Vec<&str> = SCISSOR_PLOT_INPUTS.into_iter().filter(|symbol| !params.contains_key(*symbol)).collect();
+    if missing.is_empty() {
+        write_svg(SCISSOR_PLOT_FILE_PATH, &scissor_plot(&params, &evaluation))?;
+    } else if missing.len() < SCISSOR_PLOT_INPUTS.len() {
+        println!("scissor plot: skipped, missing {}", missing.join(", "));
+    }
+    write_three_view(THREE_VIEW_FILE_PATH, &ThreeView::new(&params), &aircraft.items(w_dg))?;
 
-    println!("total weight: {} lb", total_weight.get_val());
-    println!("cg: {}", cg.x());
+    Ok(())
+}
 
+/// Conceptual stage estimate, needs only the class I parameters.
+fn class_one(params: &Params) -> Result<(), Box<dyn Error>> {
+    let class_one = ClassOne::new(params)?;
+    println!("design gross weight: {} lb", class_one.w_dg());
+    println!("empty weight: {} lb", class_one.w_e());
+    println!("fuel weight: {} lb", class_one.w_fuel());
+    println!("landing weight: {} lb", class_one.w_l());
+    println!("weight margin: {} lb", class_one.margin());
+    for (group, weight, x_cg) in class_one.groups() {
+        println!("    {}: {} lb at {} ft", group, weight, x_cg);
+    }
+    println!(
+        "empty cg: {} ft ({} % fuselage length)",
+        class_one.x_cg_empty(),
+        100. * class_one.x_cg_empty() / class_one.l(),
+    );
+    write_seeds(CLASS_ONE_SEEDS_FILE_PATH, &class_one)?;
     Ok(())
 }
 
-fn read_params_files() -> Result<Params, Box<dyn Error>> {
+/// Fits the group correction factors on the reference aircraft.
+fn calibrate_estimators(params: &Params) -> Result<(), Box<dyn Error>> {
+    let references = read_reference_aircraft(REFERENCE_AIRCRAFT_FILE_PATH)?;
+    let (factors, residuals) = calibrate(params, &references)?;
+    for residual in &residuals {
+        println!(
+            "{} {}: reference {} lb, estimate {} lb ({:.1}%), calibrated {} lb ({:.1}%)",
+            residual.aircraft(),
+            residual.group(),
+            residual.reference(),
+            residual.estimate(),
+            100. * residual.error(),
+            residual.calibrated(),
+            100. * residual.calibrated_error(),
+        );
+    }
+    for factor in &factors {
+        let (rms, rms_calibrated) = factor.rms(&residuals);
+        println!(
+            "{}: k {} (rms error {:.1}% to {:.1}%)",
+            factor.group(),
+            factor.k(),
+            100. * rms,
+            100. * rms_calibrated,
+        );
+    }
+    write_factors(CALIBRATION_FILE_PATH, &factors)?;
+    Ok(())
+}
+
+/// Ranks the parameters by their effect on the operating empty weight, its
+/// CG and static margin.
+fn sensitivity(params: &Params, stations: Option<&[FuselageStation]>, ranking: Ranking) -> Result<(), Box<dyn Error>> {
+    let sensitivities = sensitivities(params, stations, ranking);
+    println!("parameter, dln(OEW)/dln(p), dcg/dln(p) (%MAC per 1%), dSM/dln(p) (%MAC per 1%)");
+    for sensitivity in &sensitivities {
+        println!(
+            "{}: {:.5} {:.5} {:.5}",
+            sensitivity.symbol(),
+            sensitivity.oew(),
+            sensitivity.cg_mac(),
+            sensitivity.static_margin(),
+        );
+    }
+    write_sensitivities(SENSITIVITY_FILE_PATH, &sensitivities)?;
+    Ok(())
+}
+
+/// Propagates the uncertainties of the parameters to the operating empty
+/// weight, its CG and static margin.
+fn monte_carlo(
+    params: &Params,
+    stations: Option<&[FuselageStation]>,
+    samples: usize,
+    seed: u64,
+    threads: usize,
+) -> Result<(), Box<dyn Error>> {
+    let uncertainties = read_uncertainties(UNCERTAINTIES_FILE_PATH, params)?;
+    let results = run(params, stations, &uncertainties, samples, seed, threads);
+    for (name, unit, statistics) in [
+        ("OEW", "lb", Statistics::new(results.iter().map(|r| r.oew()))),
+        ("cg", "%MAC", Statistics::new(results.iter().map(|r| 100. * r.cg_mac()))),
+        ("static margin", "%MAC", Statistics::new(results.iter().map(|r| 100. * r.static_margin()))),
+    ] {
+        println!(
+            "{}: mean {} {}, sd {}, p5 {}, p50 {}, p95 {}",
+            name,
+            statistics.mean(),
+            unit,
+            statistics.sd(),
+            statistics.percentile(5.),
+            statistics.percentile(50.),
+            statistics.percentile(95.),
+        );
+    }
+    write_samples(MONTE_CARLO_FILE_PATH, &uncertainties, &results)?;
+    Ok(())
+}
+
+fn read_params_files() -> Result<(Params, Sources), Box<dyn Error>> {
     let mut params: Params = HashMap::new();
-    let mut reader = Reader::from_path(WEIGHTS_FILE_PATH)?;
-    for result in reader.records() {
-        let record = result?;
-        let symbol = record[0].to_string();
-        let value = record[1].parse::<f64>()?;
-        params.insert(symbol, value);
+    let mut sources: Sources = HashMap::new();
+    for path in [WEIGHTS_FILE_PATH, BALANCE_FILE_PATH] {
+        let mut reader = Reader::from_path(path)?;
+        for result in reader.records() {
+            let record = result?;
+            let symbol = record[0].to_string();
+            let value = record[1].parse::<f64>()?;
+            sources.insert(symbol.clone(), path.to_string());
+            params.insert(symbol, value);
+        }
     }
-    let mut reader_balance = Reader::from_path(BALANCE_FILE_PATH)?;
-    for result in reader_balance.records() {
+    Ok((params, sources))
+}
+
+
+fn read_fuselage_stations() -> Result<Vec<FuselageStation>, Box<dyn Error>> {
+    let mut stations = Vec::new();
+    let mut reader = Reader::from_path(FUSELAGE_STATIONS_FILE_PATH)?;
+    for result in reader.records() {
         let record = result?;
-        let symbol = record[0].to_string();
-        let value = record[1].parse::<f64>()?;
-        params.insert(symbol, value);
+        stations.push(FuselageStation::new(record[0].parse::<f64>()?, record[1].parse::<f64>()?));
     }
-    Ok(params)
+    Ok(stations)
 }
 
+/// Parameters of the sample aircraft, for the tests.
+#[cfg(test)]
+fn sample_params() -> Params {
+    include_str!("../tests/data/params.csv").lines()
+        .skip(1)
+        .map(|line| {
+            let (symbol, value) = line.split_once(',').expect("symbol,value");
+            (symbol.to_string(), value.parse().expect("number"))
+        })
+        .collect()
+}
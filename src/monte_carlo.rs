@@ -0,0 +1,197 @@
+use std::error::Error;
+use std::thread;
+use csv::{Reader, Writer};
+use crate::Params;
+use crate::evaluation::Evaluation;
+use crate::aerodynamics::fuselage_moment::FuselageStation;
+use crate::utils::random::Rng;
+
+#[derive(Copy, Clone)]
+pub enum Distribution {
+    Normal { mean: f64, sd: f64 },
+    Uniform { low: f64, high: f64 },
+    Triangular { low: f64, mode: f64, high: f64 },
+}
+
+impl Distribution {
+    pub fn sample(&self, rng: &mut Rng) -> f64 {
+        match *self {
+            Distribution::Normal { mean, sd } => mean + sd * rng.normal(),
+            Distribution::Uniform { low, high } => low + (high - low) * rng.uniform(),
+            Distribution::Triangular { low, mode, high } => {
+                // inverse of the cumulative distribution
+                let u = rng.uniform();
+                let f_mode = (mode - low) / (high - low);
+                if u < f_mode {
+                    low + f64::sqrt(u * (high - low) * (mode - low))
+                } else {
+                    high - f64::sqrt((1. - u) * (high - low) * (high - mode))
+                }
+            }
+        }
+    }
+}
+
+/// Distribution of one parameter.
+pub struct Uncertainty {
+    symbol: String,
+    distribution: Distribution,
+}
+
+/// Reads the uncertainties, one per row with columns symbol, distribution,
+/// a, b and c:
+/// - normal: a mean, b standard deviation
+/// - uniform: a low, b high
+/// - triangular: a low, b mode, c high
+///
+/// An empty mean or mode is the value of the parameter.
+pub fn read_uncertainties(path: &str, params: &Params) -> Result<Vec<Uncertainty>, Box<dyn Error>> {
+    let mut uncertainties = Vec::new();
+    let mut reader = Reader::from_path(path)?;
+    for result in reader.records() {
+        let record = result?;
+        let symbol = record[0].to_string();
+        let nominal = || -> Result<f64, Box<dyn Error>> {
+            params.get(&symbol).copied().ok_or_else(|| format!("{} is not a parameter, give its mean or mode", symbol).into())
+        };
+        let value = |i: usize| -> Result<Option<f64>, Box<dyn Error>> {
+            match record.get(i).unwrap_or("") {
+                "" => Ok(None),
+                field => Ok(Some(field.parse::<f64>()?)),
+            }
+        };
+        let required = |i: usize| -> Result<f64, Box<dyn Error>> {
+            value(i)?.ok_or_else(|| format!("missing bound of {}", symbol).into())
+        };
+        let distribution = match &record[1] {
+            "normal" => Distribution::Normal { mean: value(2)?.map_or_else(nominal, Ok)?, sd: required(3)? },
+            "uniform" => Distribution::Uniform { low: required(2)?, high: required(3)? },
+            "triangular" => Distribution::Triangular {
+                low: required(2)?,
+                mode: value(3)?.map_or_else(nominal, Ok)?,
+                high: required(4)?,
+            },
+            other => return Err(format!("unknown distribution {} of {}", other, symbol).into()),
+        };
+        uncertainties.push(Uncertainty { symbol, distribution });
+    }
+    Ok(uncertainties)
+}
+
+/// Sampled parameters and the outputs of one run.
+pub struct Sample {
+    values: Vec<f64>, // in the order of the uncertainties
+    oew: f64, // lb
+    cg_mac: f64, // operating empty CG (fraction of MAC)
+    static_margin: f64, // operating empty static margin (fraction of MAC)
+}
+
+impl Sample {
+    pub fn oew(&self) -> f64 {
+        self.oew
+    }
+
+    pub fn cg_mac(&self) -> f64 {
+        self.cg_mac
+    }
+
+    pub fn static_margin(&self) -> f64 {
+        self.static_margin
+    }
+}
+
+/// Runs the weight and balance on `samples` draws of the uncertain
+/// parameters. Each draw has its own generator seeded from `seed` and its
+/// index, so the results do not depend on the number of threads.
+pub fn run(
+    params: &Params,
+    stations: Option<&[FuselageStation]>,
+    uncertainties: &[Uncertainty],
+    samples: usize,
+    seed: u64,
+    threads: usize,
+) -> Vec<Sample> {
+    // the calibration factors stay the ones of the baseline
+    let params = Evaluation::new(params, stations).calibrated(params);
+    let draw = |i: usize| -> Sample {
+        let mut rng = Rng::new(seed ^ Rng::new(i as u64).next_u64());
+        let mut sampled = params.clone();
+        let values: Vec<f64> = uncertainties.iter()
+            .map(|uncertainty| {
+                let value = uncertainty.distribution.sample(&mut rng);
+                sampled.insert(uncertainty.symbol.clone(), value);
+                value
+            })
+            .collect();
+        let evaluation = Evaluation::new(&sampled, stations);
+        Sample {
+            values,
+            oew: evaluation.oew(),
+            cg_mac: evaluation.oew_cg_mac(),
+            static_margin: evaluation.static_margin(),
+        }
+    };
+    let threads = threads.clamp(1, samples.max(1));
+    let chunk = samples.div_ceil(threads);
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|t| {
+                let draw = &draw;
+                scope.spawn(move || (t * chunk..samples.min((t + 1) * chunk)).map(draw).collect::<Vec<_>>())
+            })
+            .collect();
+        handles.into_iter()
+            .flat_map(|handle| handle.join().expect("monte carlo thread panicked"))
+            .collect()
+    })
+}
+
+/// Mean, standard deviation and percentiles of one output.
+pub struct Statistics {
+    sorted: Vec<f64>,
+}
+
+impl Statistics {
+    pub fn new(values: impl Iterator<Item = f64>) -> Self {
+        let mut sorted: Vec<f64> = values.collect();
+        sorted.sort_by(f64::total_cmp);
+        Self { sorted }
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.sorted.iter().sum::<f64>() / self.sorted.len() as f64
+    }
+
+    /// Sample standard deviation
+    pub fn sd(&self) -> f64 {
+        let mean = self.mean();
+        let n = self.sorted.len() as f64;
+        f64::sqrt(self.sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.))
+    }
+
+    /// - p: percentile between 0 and 100, interpolated between the samples
+    pub fn percentile(&self, p: f64) -> f64 {
+        let rank = p / 100. * (self.sorted.len() - 1) as f64;
+        let (i, f) = (rank.floor() as usize, rank.fract());
+        match self.sorted.get(i + 1) {
+            Some(next) => self.sorted[i] + f * (next - self.sorted[i]),
+            None => self.sorted[i],
+        }
+    }
+}
+
+pub fn write_samples(path: &str, uncertainties: &[Uncertainty], samples: &[Sample]) -> Result<(), Box<dyn Error>> {
+    let mut writer = Writer::from_path(path)?;
+    let mut header = vec!["sample".to_string()];
+    header.extend(uncertainties.iter().map(|uncertainty| uncertainty.symbol.clone()));
+    header.extend(["oew", "cg_mac", "static_margin"].map(String::from));
+    writer.write_record(&header)?;
+    for (i, sample) in samples.iter().enumerate() {
+        let mut record = vec![i.to_string()];
+        record.extend(sample.values.iter().map(|value| value.to_string()));
+        record.extend([sample.oew, sample.cg_mac, sample.static_margin].map(|value| value.to_string()));
+        writer.write_record(&record)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,597 @@
+use std::cell::Cell;
+use std::error::Error;
+use csv::{Reader, Writer};
+use crate::Params;
+use crate::evaluation::{Evaluation, check_varied, update_dependents};
+use crate::class_one::ClassOne;
+use crate::aerodynamics::fuselage_moment::FuselageStation;
+
+const PENALTY: f64 = 1e4; // weight of the squared constraint violations in Nelder-Mead
+const SIMPLEX_STEP: f64 = 0.1; // size of the initial simplex, fraction of the bounds
+const GRADIENT_STEP: f64 = 1e-4; // finite difference step, fraction of the bounds
+const TOLERANCE: f64 = 1e-7;
+const DUAL_SWEEPS: usize = 500;
+const CLOSURE_ITERATIONS: usize = 20; // of the design gross weight on the mission
+
+/// Design variable between bounds.
+pub struct Variable {
+    symbol: String,
+    low: f64,
+    high: f64,
+}
+
+/// Reads the design variables, one per row with columns symbol, low and
+/// high, each a parameter which is not set from others.
+pub fn read_variables(path: &str, params: &Params) -> Result<Vec<Variable>, Box<dyn Error>> {
+    let mut variables = Vec::new();
+    let mut reader = Reader::from_path(path)?;
+    for result in reader.records() {
+        let record = result?;
+        check_varied(params, &record[0])?;
+        variables.push(Variable {
+            symbol: record[0].to_string(),
+            low: record[1].parse::<f64>()?,
+            high: record[2].parse::<f64>()?,
+        });
+    }
+    Ok(variables)
+}
+
+/// Output of an evaluation used as objective or constraint. The design gross
+/// weight stays the one of the parameters, but with the MTOW, which closes
+/// the class I mission (class_one_type, w_payload and range) around the
+/// empty weight of each evaluation, the mission fuel following.
+#[derive(Copy, Clone, PartialEq)]
+pub enum Output {
+    EmptyWeight,
+    Oew,
+    Mtow,
+    CgMac, // operating empty
+    CgFwdMac,
+    CgAftMac,
+    StaticMargin, // operating empty
+    StaticMarginFwd, // at the most forward CG
+    StaticMarginAft, // at the most aft CG
+}
+
+impl Output {
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        let output = match name {
+            "empty_weight" => Output::EmptyWeight,
+            "oew" => Output::Oew,
+            "mtow" => Output::Mtow,
+            "cg_mac" => Output::CgMac,
+            "cg_fwd_mac" => Output::CgFwdMac,
+            "cg_aft_mac" => Output::CgAftMac,
+            "static_margin" => Output::StaticMargin,
+            "static_margin_fwd" => Output::StaticMarginFwd,
+            "static_margin_aft" => Output::StaticMarginAft,
+            _ => return Err(format!("unknown output {}", name)),
+        };
+        Ok(output)
+    }
+
+    pub fn value(&self, evaluation: &Evaluation) -> f64 {
+        match self {
+            Output::EmptyWeight => evaluation.empty_weight(),
+            Output::Oew => evaluation.oew(),
+            Output::Mtow => evaluation.w_dg(),
+            Output::CgMac => evaluation.oew_cg_mac(),
+            Output::CgFwdMac => evaluation.cg_range_mac().0,
+            Output::CgAftMac => evaluation.cg_range_mac().1,
+            Output::StaticMargin => evaluation.static_margin(),
+            Output::StaticMarginFwd => evaluation.static_margin_range().0,
+            Output::StaticMarginAft => evaluation.static_margin_range().1,
+        }
+    }
+
+    /// lb for the weights, MAC for the CG and static margins in fraction of MAC
+    pub fn unit(&self) -> &'static str {
+        match self {
+            Output::EmptyWeight | Output::Oew | Output::Mtow => "lb",
+            _ => "MAC",
+        }
+    }
+}
+
+/// Bounds on an output, CG and static margins in fraction of MAC.
+pub struct Constraint {
+    output: Output,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+/// Reads the constraints, one per row with columns output, min and max,
+/// an empty bound being unconstrained.
+pub fn read_constraints(path: &str) -> Result<Vec<Constraint>, Box<dyn Error>> {
+    let mut constraints = Vec::new();
+    let mut reader = Reader::from_path(path)?;
+    for result in reader.records() {
+        let record = result?;
+        let bound = |i: usize| -> Result<Option<f64>, Box<dyn Error>> {
+            match record.get(i).unwrap_or("") {
+                "" => Ok(None),
+                field => Ok(Some(field.parse::<f64>()?)),
+            }
+        };
+        constraints.push(Constraint { output: Output::from_name(&record[0])?, min: bound(1)?, max: bound(2)? });
+    }
+    Ok(constraints)
+}
+
+/// Minimisation of an output over the design variables, which are scaled
+/// to [0, 1] between their bounds.
+pub struct Problem<'a> {
+    params: Params,
+    stations: Option<&'a [FuselageStation]>,
+    variables: Vec<Variable>,
+    objective: Output,
+    constraints: Vec<Constraint>,
+    mission: Option<ClassOne>, // closes the design gross weight, MTOW only
+    scale: f64, // objective at the start
+    evaluations: Cell<usize>,
+}
+
+impl<'a> Problem<'a> {
+    pub fn new(
+        params: &Params,
+        stations: Option<&'a [FuselageStation]>,
+        variables: Vec<Variable>,
+        objective: Output,
+        constraints: Vec<Constraint>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let mtow = objective == Output::Mtow || constraints.iter().any(|c| c.output == Output::Mtow);
+        let mission = match (mtow, params.contains_key("class_one_type")) {
+            (false, _) => None,
+            (true, true) => Some(ClassOne::new(params)?),
+            (true, false) => return Err("mtow needs the class I mission, class_one_type, w_payload and range".into()),
+        };
+        // the calibration factors stay the ones of the baseline
+        let baseline = Evaluation::new(params, stations);
+        let mut problem = Self {
+            params: baseline.calibrated(params),
+            stations,
+            variables,
+            objective,
+            constraints,
+            mission,
+            scale: 1.,
+            evaluations: Cell::new(0),
+        };
+        problem.scale = objective.value(&problem.evaluation(problem.params.clone())).abs().max(f64::EPSILON);
+        Ok(problem)
+    }
+
+    /// Scaled values of the variables in the parameters, within the bounds.
+    fn start(&self) -> Vec<f64> {
+        self.variables.iter()
+            .map(|v| ((self.params[&v.symbol] - v.low) / (v.high - v.low)).clamp(0., 1.))
+            .collect()
+    }
+
+    fn params_at(&self, x: &[f64]) -> Params {
+        let mut params = self.params.clone();
+        for (v, x) in self.variables.iter().zip(x) {
+            params.insert(v.symbol.clone(), v.low + x.clamp(0., 1.) * (v.high - v.low));
+        }
+        for v in &self.variables {
+            update_dependents(&mut params, &v.symbol);
+        }
+        params
+    }
+
+    /// Evaluation of the parameters, with the design gross weight and the
+    /// mission fuel closing the mission when the MTOW is used.
+    fn evaluation(&self, mut params: Params) -> Evaluation {
+        if let Some(mission) = &self.mission {
+            for _ in 0..CLOSURE_ITERATIONS {
+                let w_e = Evaluation::new(&params, self.stations).empty_weight();
+                let w_dg = mission.gross_weight(w_e);
+                let converged = (w_dg - params.get("w_dg").copied().unwrap_or(0.)).abs() < TOLERANCE * w_dg;
+                params.insert("w_dg".to_string(), w_dg);
+                params.insert("w_fuel".to_string(), mission.fuel_fraction() * w_dg);
+                if converged {
+                    break;
+                }
+            }
+        }
+        Evaluation::new(&params, self.stations)
+    }
+
+    /// Scaled objective and constraint functions, feasible when all are
+    /// positive.
+    fn evaluate(&self, x: &[f64]) -> (f64, Vec<f64>) {
+        self.evaluations.set(self.evaluations.get() + 1);
+        let evaluation = self.evaluation(self.params_at(x));
+        let mut g = Vec::new();
+        for constraint in &self.constraints {
+            let value = constraint.output.value(&evaluation);
+            if let Some(min) = constraint.min {
+                g.push(value - min);
+            }
+            if let Some(max) = constraint.max {
+                g.push(max - value);
+            }
+        }
+        (self.objective.value(&evaluation) / self.scale, g)
+    }
+
+    fn solution(&self, x: &[f64], iterations: usize) -> Solution {
+        let evaluation = self.evaluation(self.params_at(x));
+        let (_, g) = self.evaluate(x);
+        Solution {
+            values: self.variables.iter()
+                .zip(x)
+                .map(|(v, x)| (v.symbol.clone(), v.low + x.clamp(0., 1.) * (v.high - v.low)))
+                .collect(),
+            objective: self.objective.value(&evaluation),
+            feasible: g.iter().all(|g| *g >= -TOLERANCE),
+            iterations,
+            evaluations: self.evaluations.get(),
+        }
+    }
+}
+
+pub struct Solution {
+    values: Vec<(String, f64)>, // design variables
+    objective: f64,
+    feasible: bool,
+    iterations: usize,
+    evaluations: usize,
+}
+
+impl Solution {
+    pub fn values(&self) -> &[(String, f64)] {
+        &self.values
+    }
+
+    pub fn objective(&self) -> f64 {
+        self.objective
+    }
+
+    pub fn feasible(&self) -> bool {
+        self.feasible
+    }
+
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    pub fn evaluations(&self) -> usize {
+        self.evaluations
+    }
+}
+
+//---------------------------------------------------- Nelder-Mead
+
+/// Derivative free simplex search, the constraints as a quadratic penalty
+/// and the bounds by clamping.
+pub fn nelder_mead(problem: &Problem, max_iterations: usize) -> Solution {
+    let (x, iterations) = simplex_search(|x| problem.evaluate(x), problem.start(), max_iterations);
+    problem.solution(&x, iterations)
+}
+
+/// Minimum and number of iterations from x_0, evaluate giving the objective
+/// and the constraints, feasible when positive, at scaled variables in [0, 1].
+fn simplex_search(
+    evaluate: impl Fn(&[f64]) -> (f64, Vec<f64>),
+    x_0: Vec<f64>,
+    max_iterations: usize,
+) -> (Vec<f64>, usize) {
+    let f = |x: &[f64]| -> f64 {
+        let (f, g) = evaluate(x);
+        f + PENALTY * g.iter().map(|g| g.min(0.).powi(2)).sum::<f64>()
+    };
+    let clamp = |x: Vec<f64>| -> Vec<f64> { x.into_iter().map(|x| x.clamp(0., 1.)).collect() };
+    let n = x_0.len();
+    let mut simplex: Vec<(Vec<f64>, f64)> = vec![(x_0.clone(), f(&x_0))];
+    for j in 0..n {
+        let mut x = x_0.clone();
+        x[j] += if x[j] + SIMPLEX_STEP <= 1. { SIMPLEX_STEP } else { -SIMPLEX_STEP };
+        let value = f(&x);
+        simplex.push((x, value));
+    }
+
+    let mut iterations = 0;
+    while iterations < max_iterations {
+        iterations += 1;
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+        if simplex[n].1 - simplex[0].1 < TOLERANCE {
+            break;
+        }
+        let centroid: Vec<f64> = (0..n)
+            .map(|j| simplex[..n].iter().map(|(x, _)| x[j]).sum::<f64>() / n as f64)
+            .collect();
+        let towards = |t: f64| -> Vec<f64> {
+            clamp(centroid.iter().zip(&simplex[n].0).map(|(c, w)| c + t * (c - w)).collect())
+        };
+        let reflected = towards(1.);
+        let f_reflected = f(&reflected);
+        if f_reflected < simplex[0].1 {
+            let expanded = towards(2.);
+            let f_expanded = f(&expanded);
+            simplex[n] = if f_expanded < f_reflected { (expanded, f_expanded) } else { (reflected, f_reflected) };
+        } else if f_reflected < simplex[n - 1].1 {
+            simplex[n] = (reflected, f_reflected);
+        } else {
+            let contracted = if f_reflected < simplex[n].1 { towards(0.5) } else { towards(-0.5) };
+            let f_contracted = f(&contracted);
+            if f_contracted < simplex[n].1.min(f_reflected) {
+                simplex[n] = (contracted, f_contracted);
+            } else {
+                // shrink towards the best point
+                let best = simplex[0].0.clone();
+                for (x, value) in simplex.iter_mut().skip(1) {
+                    *x = best.iter().zip(x.iter()).map(|(b, x)| b + 0.5 * (x - b)).collect();
+                    *value = f(x);
+                }
+            }
+        }
+    }
+    simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+    (simplex.swap_remove(0).0, iterations)
+}
+
+//---------------------------------------------------- sequential quadratic programming
+
+/// Sequential quadratic programming in the manner of SLSQP: finite
+/// difference gradients, damped BFGS approximation of the Hessian of the
+/// Lagrangian, quadratic subproblems with the linearised constraints solved
+/// in their dual, and an L1 merit function line search.
+pub fn sqp(problem: &Problem, max_iterations: usize) -> Solution {
+    let (x, iterations) = sequential_quadratic(|x| problem.evaluate(x), problem.start(), max_iterations);
+    problem.solution(&x, iterations)
+}
+
+/// Same as `simplex_search` for the SQP method.
+fn sequential_quadratic(
+    evaluate: impl Fn(&[f64]) -> (f64, Vec<f64>),
+    x_0: Vec<f64>,
+    max_iterations: usize,
+) -> (Vec<f64>, usize) {
+    let mut x = x_0;
+    let n = x.len();
+    let linearise = |x: &[f64], f: f64, g: &[f64]| -> (Vec<f64>, Vec<Vec<f64>>) {
+        let mut df = vec![0.; n];
+        let mut dg = vec![vec![0.; n]; g.len()];
+        for j in 0..n {
+            let mut x_h = x.to_vec();
+            let h = if x[j] + GRADIENT_STEP <= 1. { GRADIENT_STEP } else { -GRADIENT_STEP };
+            x_h[j] += h;
+            let (f_h, g_h) = evaluate(&x_h);
+            df[j] = (f_h - f) / h;
+            for (i, row) in dg.iter_mut().enumerate() {
+                row[j] = (g_h[i] - g[i]) / h;
+            }
+        }
+        (df, dg)
+    };
+    // constraints with the bounds 0 <= x <= 1 appended
+    let with_bounds = |x: &[f64], g: &[f64], dg: &[Vec<f64>]| -> (Vec<f64>, Vec<Vec<f64>>) {
+        let mut c = g.to_vec();
+        let mut a = dg.to_vec();
+        for j in 0..n {
+            let mut e = vec![0.; n];
+            e[j] = 1.;
+            c.push(x[j]);
+            a.push(e.clone());
+            c.push(1. - x[j]);
+            a.push(e.iter().map(|e| -e).collect());
+        }
+        (c, a)
+    };
+
+    let (mut f, mut g) = evaluate(&x);
+    let (mut df, mut dg) = linearise(&x, f, &g);
+    let mut b = identity(n);
+    let mut mu: f64 = 1.;
+    let mut iterations = 0;
+    while iterations < max_iterations {
+        iterations += 1;
+        let (c, a) = with_bounds(&x, &g, &dg);
+        // steepest descent, restarting the BFGS approximation, when B is singular
+        let h = match inverse(&b) {
+            Some(h) => h,
+            None => {
+                b = identity(n);
+                identity(n)
+            }
+        };
+        let lambda = dual(&h, &a, &df, &c);
+        // d = H (A^T lambda - df)
+        let rhs: Vec<f64> = (0..n)
+            .map(|j| a.iter().zip(&lambda).map(|(row, l)| row[j] * l).sum::<f64>() - df[j])
+            .collect();
+        let d = mat_vec(&h, &rhs);
+        if d.iter().all(|d| d.abs() < TOLERANCE) {
+            break;
+        }
+
+        mu = lambda.iter().fold(mu, |mu, l| mu.max(1.5 * l));
+        let violation = |g: &[f64]| g.iter().map(|g| (-g).max(0.)).sum::<f64>();
+        let merit = f + mu * violation(&g);
+        let slope = dot(&df, &d) - mu * violation(&g);
+        let mut alpha = 1.;
+        let (mut x_new, mut f_new, mut g_new);
+        loop {
+            x_new = x.iter().zip(&d).map(|(x, d)| (x + alpha * d).clamp(0., 1.)).collect::<Vec<f64>>();
+            (f_new, g_new) = evaluate(&x_new);
+            if f_new + mu * violation(&g_new) <= merit + 0.1 * alpha * slope.min(0.) || alpha < 1e-4 {
+                break;
+            }
+            alpha *= 0.5;
+        }
+
+        let (df_new, dg_new) = linearise(&x_new, f_new, &g_new);
+        // BFGS on the gradient of the Lagrangian, damped to keep B positive definite
+        let grad_lagrangian = |df: &[f64], dg: &[Vec<f64>]| -> Vec<f64> {
+            (0..n)
+                .map(|j| df[j] - dg.iter().zip(&lambda).map(|(row, l)| row[j] * l).sum::<f64>())
+                .collect()
+        };
+        let s: Vec<f64> = x_new.iter().zip(&x).map(|(a, b)| a - b).collect();
+        let mut y: Vec<f64> = grad_lagrangian(&df_new, &dg_new).iter()
+            .zip(grad_lagrangian(&df, &dg))
+            .map(|(a, b)| a - b)
+            .collect();
+        let bs = mat_vec(&b, &s);
+        let sbs = dot(&s, &bs);
+        if sbs > f64::EPSILON {
+            let sy = dot(&s, &y);
+            if sy < 0.2 * sbs {
+                let theta = 0.8 * sbs / (sbs - sy);
+                y = y.iter().zip(&bs).map(|(y, bs)| theta * y + (1. - theta) * bs).collect();
+            }
+            let sy = dot(&s, &y);
+            for i in 0..n {
+                for j in 0..n {
+                    b[i][j] += y[i] * y[j] / sy - bs[i] * bs[j] / sbs;
+                }
+            }
+        }
+        x = x_new;
+        (f, g, df, dg) = (f_new, g_new, df_new, dg_new);
+    }
+    (x, iterations)
+}
+
+/// Multipliers of min 1/2 d^T B d + df^T d subject to A d + c >= 0, by
+/// projected coordinate descent on the dual
+/// min 1/2 l^T (A H A^T) l + l^T (c - A H df), l >= 0, with H = B^-1.
+fn dual(h: &[Vec<f64>], a: &[Vec<f64>], df: &[f64], c: &[f64]) -> Vec<f64> {
+    let ah: Vec<Vec<f64>> = a.iter().map(|row| mat_vec(h, row)).collect(); // rows of A H (H symmetric)
+    let m: Vec<Vec<f64>> = ah.iter().map(|ah_i| a.iter().map(|a_j| dot(ah_i, a_j)).collect()).collect();
+    let q: Vec<f64> = c.iter().zip(&ah).map(|(c, ah_i)| c - dot(ah_i, df)).collect();
+    let mut lambda = vec![0.; c.len()];
+    for _ in 0..DUAL_SWEEPS {
+        for i in 0..lambda.len() {
+            if m[i][i] <= f64::EPSILON {
+                continue;
+            }
+            let gradient = dot(&m[i], &lambda) + q[i];
+            lambda[i] = (lambda[i] - gradient / m[i][i]).max(0.);
+        }
+    }
+    lambda
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+fn mat_vec(m: &[Vec<f64>], v: &[f64]) -> Vec<f64> {
+    m.iter().map(|row| dot(row, v)).collect()
+}
+
+fn identity(n: usize) -> Vec<Vec<f64>> {
+    (0..n).map(|i| (0..n).map(|j| if i == j { 1. } else { 0. }).collect()).collect()
+}
+
+/// Gauss-Jordan elimination with partial pivoting, none when singular
+fn inverse(m: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    let n = m.len();
+    let mut a = m.to_vec();
+    let mut inv = identity(n);
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs())).expect("empty matrix");
+        a.swap(col, pivot);
+        inv.swap(col, pivot);
+        let p = a[col][col];
+        if p.abs() < f64::EPSILON {
+            return None;
+        }
+        for j in 0..n {
+            a[col][j] /= p;
+            inv[col][j] /= p;
+        }
+        for i in (0..n).filter(|&i| i != col) {
+            let factor = a[i][col];
+            for j in 0..n {
+                a[i][j] -= factor * a[col][j];
+                inv[i][j] -= factor * inv[col][j];
+            }
+        }
+    }
+    Some(inv)
+}
+
+/// Writes the optimum design variables in the format of the parameters files.
+pub fn write_solution(path: &str, solution: &Solution) -> Result<(), Box<dyn Error>> {
+    let mut writer = Writer::from_path(path)?;
+    writer.write_record(["symbol", "value"])?;
+    for (symbol, value) in &solution.values {
+        writer.write_record([symbol.clone(), value.to_string()])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample_params;
+
+    fn distance(x: &[f64], expected: &[f64]) -> f64 {
+        x.iter().zip(expected).map(|(x, e)| (x - e).powi(2)).sum::<f64>().sqrt()
+    }
+
+    // minimum at (0.3, 0.7) inside the bounds
+    fn quadratic(x: &[f64]) -> (f64, Vec<f64>) {
+        ((x[0] - 0.3).powi(2) + 2. * (x[1] - 0.7).powi(2), vec![])
+    }
+
+    // unconstrained minimum at (0.8, 0.8), x_0 + x_1 <= 1 moves it to (0.5, 0.5)
+    fn constrained(x: &[f64]) -> (f64, Vec<f64>) {
+        ((x[0] - 0.8).powi(2) + (x[1] - 0.8).powi(2), vec![1. - x[0] - x[1]])
+    }
+
+    // unconstrained minimum at (1.5, 0.4), the bound moves it to (1, 0.4)
+    fn bounded(x: &[f64]) -> (f64, Vec<f64>) {
+        ((x[0] - 1.5).powi(2) + (x[1] - 0.4).powi(2), vec![])
+    }
+
+    #[test]
+    fn nelder_mead_finds_quadratic_minimum() {
+        let (x, _) = simplex_search(quadratic, vec![0.9, 0.1], 500);
+        assert!(distance(&x, &[0.3, 0.7]) < 1e-3, "{:?}", x);
+    }
+
+    #[test]
+    fn nelder_mead_respects_constraints_and_bounds() {
+        let (x, _) = simplex_search(constrained, vec![0.1, 0.2], 500);
+        assert!(distance(&x, &[0.5, 0.5]) < 1e-3, "{:?}", x);
+        let (x, _) = simplex_search(bounded, vec![0.5, 0.5], 500);
+        assert!(distance(&x, &[1., 0.4]) < 1e-3, "{:?}", x);
+    }
+
+    #[test]
+    fn sqp_finds_quadratic_minimum() {
+        let (x, _) = sequential_quadratic(quadratic, vec![0.9, 0.1], 100);
+        assert!(distance(&x, &[0.3, 0.7]) < 1e-4, "{:?}", x);
+    }
+
+    #[test]
+    fn sqp_respects_constraints_and_bounds() {
+        let (x, _) = sequential_quadratic(constrained, vec![0.1, 0.2], 100);
+        assert!(distance(&x, &[0.5, 0.5]) < 1e-4, "{:?}", x);
+        assert!(constrained(&x).1[0] >= -1e-6);
+        let (x, _) = sequential_quadratic(bounded, vec![0.5, 0.5], 100);
+        assert!(distance(&x, &[1., 0.4]) < 1e-4, "{:?}", x);
+    }
+
+    #[test]
+    fn inverse_of_singular_matrix_is_none() {
+        assert!(inverse(&[vec![1., 2.], vec![2., 4.]]).is_none());
+        let inv = inverse(&[vec![2., 1.], vec![1., 1.]]).unwrap();
+        assert!(distance(&inv.concat(), &[1., -1., -1., 2.]) < 1e-12);
+    }
+
+    #[test]
+    fn mtow_closes_the_mission() {
+        let params = sample_params();
+        let problem = Problem::new(&params, None, vec![], Output::Mtow, vec![]).unwrap();
+        let evaluation = problem.evaluation(problem.params.clone());
+        let w_dg = ClassOne::new(&params).unwrap().gross_weight(evaluation.empty_weight());
+        assert!((evaluation.w_dg() - w_dg).abs() < 1e-6 * w_dg, "{} != {}", evaluation.w_dg(), w_dg);
+    }
+}
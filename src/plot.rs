@@ -0,0 +1,313 @@
+use std::error::Error;
+use std::fs;
+use crate::Params;
+use crate::components::Item;
+use crate::components::varying::{LoadCase, Passengers};
+use crate::evaluation::{Evaluation, NUM_PASSENGERS, PASSENGERS_ABREAST, passenger_weight, fuel, cargo};
+
+const WIDTH: f64 = 640.;
+const HEIGHT: f64 = 480.;
+const MARGIN: f64 = 60.; // around the plot area, for the axes labels
+const TICKS: usize = 6; // approximate number of ticks per axis
+const COLOURS: [&str; 6] = ["#1f77b4", "#d62728", "#2ca02c", "#ff7f0e", "#9467bd", "#8c564b"];
+const SCISSOR_POINTS: usize = 20;
+
+pub enum Style {
+    Line,
+    Dashed,
+    Markers,
+    Polygon, // closed and filled
+    Arrow, // line with a head at the last point
+}
+
+/// Data drawn with one style and colour.
+pub struct Series {
+    name: String,
+    points: Vec<(f64, f64)>,
+    labels: Vec<String>, // next to the points, may be empty
+    style: Style,
+}
+
+impl Series {
+    pub fn new(name: &str, points: Vec<(f64, f64)>, style: Style) -> Self {
+        Self { name: name.to_string(), points, labels: vec![], style }
+    }
+
+    pub fn with_labels(mut self, labels: Vec<String>) -> Self {
+        self.labels = labels;
+        self
+    }
+}
+
+/// Two dimensional chart written as SVG.
+pub struct Chart {
+    title: String,
+    x_label: String,
+    y_label: String,
+    series: Vec<Series>,
+}
+
+impl Chart {
+    pub fn new(title: &str, x_label: &str, y_label: &str) -> Self {
+        Self { title: title.to_string(), x_label: x_label.to_string(), y_label: y_label.to_string(), series: vec![] }
+    }
+
+    pub fn add(&mut self, series: Series) {
+        self.series.push(series);
+    }
+
+    /// Bounds of the data, widened to whole tick steps.
+    fn bounds(&self) -> ((f64, f64, f64), (f64, f64, f64)) {
+        let points = self.series.iter().flat_map(|series| series.points.iter());
+        let (mut x_min, mut x_max, mut y_min, mut y_max) = (f64::MAX, f64::MIN, f64::MAX, f64::MIN);
+        for (x, y) in points {
+            (x_min, x_max, y_min, y_max) = (x_min.min(*x), x_max.max(*x), y_min.min(*y), y_max.max(*y));
+        }
+        if x_min > x_max {
+            (x_min, x_max, y_min, y_max) = (0., 1., 0., 1.);
+        }
+        (axis(x_min, x_max), axis(y_min, y_max))
+    }
+
+    pub fn to_svg(&self) -> String {
+        let ((x_min, x_max, x_step), (y_min, y_max, y_step)) = self.bounds();
+        let sx = |x: f64| MARGIN + (x - x_min) / (x_max - x_min) * (WIDTH - 2. * MARGIN);
+        let sy = |y: f64| HEIGHT - MARGIN - (y - y_min) / (y_max - y_min) * (HEIGHT - 2. * MARGIN);
+        let mut svg = String::new();
+        let mut w = |s: String| svg.push_str(&s);
+
+        w(format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" \
+             font-family=\"sans-serif\" font-size=\"11\">\n",
+            WIDTH, HEIGHT, WIDTH, HEIGHT,
+        ));
+        w(format!("<rect width=\"{}\" height=\"{}\" fill=\"white\"/>\n", WIDTH, HEIGHT));
+        w(format!(
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" font-size=\"14\">{}</text>\n",
+            WIDTH / 2., MARGIN / 2., escape(&self.title),
+        ));
+
+        // grid and ticks
+        for x in steps(x_min, x_max, x_step) {
+            w(format!(
+                "<line x1=\"{0:.1}\" y1=\"{1:.1}\" x2=\"{0:.1}\" y2=\"{2:.1}\" stroke=\"#ddd\"/>\n\
+                 <text x=\"{0:.1}\" y=\"{3:.1}\" text-anchor=\"middle\">{4}</text>\n",
+                sx(x), sy(y_min), sy(y_max), sy(y_min) + 15., tick_label(x, x_step),
+            ));
+        }
+        for y in steps(y_min, y_max, y_step) {
+            w(format!(
+                "<line x1=\"{1:.1}\" y1=\"{0:.1}\" x2=\"{2:.1}\" y2=\"{0:.1}\" stroke=\"#ddd\"/>\n\
+                 <text x=\"{3:.1}\" y=\"{0:.1}\" text-anchor=\"end\" dominant-baseline=\"middle\">{4}</text>\n",
+                sy(y), sx(x_min), sx(x_max), sx(x_min) - 5., tick_label(y, y_step),
+            ));
+        }
+        w(format!(
+            "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"none\" stroke=\"black\"/>\n",
+            MARGIN, MARGIN, WIDTH - 2. * MARGIN, HEIGHT - 2. * MARGIN,
+        ));
+        w(format!(
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n",
+            WIDTH / 2., HEIGHT - 15., escape(&self.x_label),
+        ));
+        w(format!(
+            "<text x=\"15\" y=\"{0}\" text-anchor=\"middle\" transform=\"rotate(-90 15 {0})\">{1}</text>\n",
+            HEIGHT / 2., escape(&self.y_label),
+        ));
+
+        for (i, series) in self.series.iter().enumerate() {
+            let colour = COLOURS[i % COLOURS.len()];
+            match series.style {
+                Style::Line | Style::Dashed | Style::Arrow => {
+                    let points: Vec<String> = series.points.iter()
+                        .map(|(x, y)| format!("{:.1},{:.1}", sx(*x), sy(*y)))
+                        .collect();
+                    let dash = if let Style::Dashed = series.style { " stroke-dasharray=\"6 4\"" } else { "" };
+                    w(format!(
+                        "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"1.5\"{}/>\n",
+                        points.join(" "), colour, dash,
+                    ));
+                }
+                Style::Polygon => {
+                    let points: Vec<String> = series.points.iter()
+                        .map(|(x, y)| format!("{:.1},{:.1}", sx(*x), sy(*y)))
+                        .collect();
+                    w(format!(
+                        "<polygon points=\"{}\" fill=\"{1}\" fill-opacity=\"0.15\" stroke=\"{1}\"/>\n",
+                        points.join(" "), colour,
+                    ));
+                }
+                Style::Markers => {
+                    for (x, y) in &series.points {
+                        w(format!("<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"3\" fill=\"{}\"/>\n", sx(*x), sy(*y), colour));
+                    }
+                }
+            }
+            if let (Style::Arrow, [.., (x_0, y_0), (x_1, y_1)]) = (&series.style, series.points.as_slice()) {
+                // head along the last segment, in screen coordinates
+                let (x_1, y_1) = (sx(*x_1), sy(*y_1));
+                let angle = f64::atan2(y_1 - sy(*y_0), x_1 - sx(*x_0));
+                let head = |a: f64| format!("{:.1},{:.1}", x_1 - 10. * f64::cos(angle + a), y_1 - 10. * f64::sin(angle + a));
+                w(format!(
+                    "<polygon points=\"{:.1},{:.1} {} {}\" fill=\"{}\"/>\n",
+                    x_1, y_1, head(0.4), head(-0.4), colour,
+                ));
+            }
+            for ((x, y), label) in series.points.iter().zip(&series.labels) {
+                w(format!(
+                    "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"9\" fill=\"{}\">{}</text>\n",
+                    sx(*x) + 5., sy(*y) - 5., colour, escape(label),
+                ));
+            }
+            // legend
+            let y = MARGIN + 10. + 15. * i as f64;
+            w(format!(
+                "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"10\" height=\"10\" fill=\"{}\"/>\n\
+                 <text x=\"{:.1}\" y=\"{:.1}\">{}</text>\n",
+                WIDTH - MARGIN - 150., y - 9., colour, WIDTH - MARGIN - 135., y, escape(&series.name),
+            ));
+        }
+        w("</svg>\n".to_string());
+        svg
+    }
+}
+
+/// Lower bound, upper bound and step of an axis covering [min, max].
+fn axis(min: f64, max: f64) -> (f64, f64, f64) {
+    let span = if max > min { max - min } else { min.abs().max(1.) };
+    let raw = span / TICKS as f64;
+    let magnitude = 10f64.powf(raw.log10().floor());
+    let step = [1., 2., 5., 10.].iter()
+        .map(|m| m * magnitude)
+        .find(|step| *step >= raw)
+        .unwrap_or(10. * magnitude);
+    let low = (min / step).floor() * step;
+    let mut high = (max / step).ceil() * step;
+    if high <= low {
+        high = low + step;
+    }
+    (low, high, step)
+}
+
+fn steps(min: f64, max: f64, step: f64) -> impl Iterator<Item = f64> {
+    let n = ((max - min) / step).round() as usize;
+    (0..=n).map(move |i| min + i as f64 * step)
+}
+
+fn tick_label(value: f64, step: f64) -> String {
+    let decimals = (-step.log10().floor()).max(0.) as usize;
+    format!("{:.*}", decimals, value)
+}
+
+pub fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Weight and CG (%MAC) of a set of items.
+fn loading_point(evaluation: &Evaluation, items: &[Item]) -> (f64, f64) {
+    let weight: f64 = items.iter().map(|item| item.weight().get_val()).sum();
+    let moment: f64 = items.iter().map(|item| *item.pos_times_weight().x()).sum();
+    (100. * evaluation.cg_mac(moment / weight), weight)
+}
+
+/// Weight against CG: the CG envelope, the loading conditions, the loops of
+/// passengers boarding row by row from the front and from the rear, then the
+/// cargo and fuel vectors.
+///
+/// The envelope is bounded by the CG limits between w_envelope_min (default
+/// the operating empty weight) and w_envelope_max (default the design gross
+/// weight). Unless cg_fwd_limit and cg_aft_limit are both given, the limits
+/// are the most forward condition and the neutral point less the minimum
+/// static margin, and the envelope is labelled as derived.
+pub fn loading_diagram(params: &Params, evaluation: &Evaluation) -> Chart {
+    let mut chart = Chart::new("Loading diagram", "CG (%MAC)", "weight (lb)");
+    let conditions = evaluation.conditions();
+
+    let (fwd, aft) = evaluation.cg_limits_mac(params);
+    let w_min = params.get("w_envelope_min").copied().unwrap_or_else(|| evaluation.oew());
+    let w_max = params.get("w_envelope_max").copied().unwrap_or_else(|| evaluation.w_dg());
+    let given = params.contains_key("cg_fwd_limit") && params.contains_key("cg_aft_limit");
+    chart.add(Series::new(
+        if given { "CG envelope" } else { "CG range (derived)" },
+        vec![(100. * fwd, w_min), (100. * fwd, w_max), (100. * aft, w_max), (100. * aft, w_min)],
+        Style::Polygon,
+    ));
+
+    let operating_empty = evaluation.condition("operating empty").items().to_vec();
+    let rows = (NUM_PASSENGERS / PASSENGERS_ABREAST).round() as usize;
+    for (name, load_case) in [("boarding front to rear", LoadCase::Front), ("boarding rear to front", LoadCase::Rear)] {
+        let mut points = vec![loading_point(evaluation, &operating_empty)];
+        for row in 1..=rows {
+            let passengers = Passengers::new(row as f64 * PASSENGERS_ABREAST, passenger_weight(), load_case);
+            let mut items = operating_empty.clone();
+            items.push(passengers.item());
+            points.push(loading_point(evaluation, &items));
+        }
+        chart.add(Series::new(name, points, Style::Line));
+    }
+    let mut items = operating_empty;
+    items.push(Passengers::new(NUM_PASSENGERS, passenger_weight(), LoadCase::Center).item());
+    let zero_cargo = loading_point(evaluation, &items);
+    items.push(cargo(params).item());
+    let zero_fuel = loading_point(evaluation, &items);
+    chart.add(Series::new("cargo", vec![zero_cargo, zero_fuel], Style::Arrow));
+    items.push(fuel(params).item());
+    chart.add(Series::new("fuel vector", vec![zero_fuel, loading_point(evaluation, &items)], Style::Arrow));
+
+    chart.add(
+        Series::new(
+            "loading conditions",
+            conditions.iter()
+                .map(|condition| (100. * evaluation.cg_mac(*condition.cg().x()), condition.weight()))
+                .collect(),
+            Style::Markers,
+        )
+        .with_labels(conditions.iter().map(|condition| condition.name().to_string()).collect()),
+    );
+    chart
+}
+
+// landing aerodynamic coefficients the scissor plot needs
+pub const SCISSOR_PLOT_INPUTS: [&str; 3] = ["c_m_ac", "c_l_landing", "c_l_h_max"];
+
+/// Horizontal tailplane area ratio against CG (%MAC): the neutral point and
+/// stability limit, the forward limit from the controllability on landing
+/// and the CG range at the current area ratio.
+///
+/// Needs c_m_ac, the pitching moment coefficient of the wing and fuselage,
+/// c_l_landing, the lift coefficient of the aircraft less tail on landing,
+/// and c_l_h_max, the maximum (negative) tailplane lift coefficient.
+pub fn scissor_plot(params: &Params, evaluation: &Evaluation) -> Chart {
+    let mut chart = Chart::new("Scissor plot", "CG (%MAC)", "Sh/S");
+    let stability = evaluation.stability();
+    let c_m_ac = *params.get("c_m_ac").expect("missing c_m_ac");
+    let c_l = *params.get("c_l_landing").expect("missing c_l_landing");
+    let c_l_h = *params.get("c_l_h_max").expect("missing c_l_h_max");
+    let static_margin_min = params.get("static_margin_min").copied().unwrap_or(0.05);
+
+    let s_w = stability.s_w();
+    let ratio = stability.s_h() / s_w;
+    let ratios: Vec<f64> = (0..=SCISSOR_POINTS).map(|i| 2. * ratio * i as f64 / SCISSOR_POINTS as f64).collect();
+    let line = |x: &dyn Fn(f64) -> f64| -> Vec<(f64, f64)> {
+        ratios.iter().map(|r| (100. * evaluation.cg_mac(x(r * s_w)), *r)).collect()
+    };
+    chart.add(Series::new("neutral point", line(&|s_h| stability.x_np_with_tail(s_h)), Style::Dashed));
+    chart.add(Series::new(
+        "stability limit",
+        line(&|s_h| stability.x_np_with_tail(s_h) - static_margin_min * stability.c_bar()),
+        Style::Line,
+    ));
+    chart.add(Series::new(
+        "controllability limit",
+        line(&|s_h| stability.x_cg_controllable(s_h, c_m_ac, c_l, c_l_h)),
+        Style::Line,
+    ));
+    let (fwd, aft) = evaluation.cg_range_mac();
+    chart.add(Series::new("CG range", vec![(100. * fwd, ratio), (100. * aft, ratio)], Style::Line));
+    chart
+}
+
+pub fn write_svg(path: &str, chart: &Chart) -> Result<(), Box<dyn Error>> {
+    fs::write(path, chart.to_svg())?;
+    Ok(())
+}
@@ -0,0 +1,299 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use crate::{Params, Sources};
+use crate::contingency::Contingency;
+use crate::evaluation::Evaluation;
+use crate::loading::cg_limits;
+use crate::plot::loading_diagram;
+use crate::layout::ThreeView;
+
+#[derive(Copy, Clone)]
+pub enum Format {
+    Markdown,
+    Html,
+    Latex,
+}
+
+impl Format {
+    pub const ALL: [Format; 3] = [Format::Markdown, Format::Html, Format::Latex];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Markdown => "md",
+            Format::Html => "html",
+            Format::Latex => "tex",
+        }
+    }
+
+    fn escape(&self, s: &str) -> String {
+        match self {
+            Format::Markdown => s.replace('|', "\\|"),
+            Format::Html => s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;"),
+            Format::Latex => s.chars()
+                .map(|c| match c {
+                    '\\' => "\\textbackslash{}".to_string(),
+                    '~' => "\\textasciitilde{}".to_string(),
+                    '^' => "\\textasciicircum{}".to_string(),
+                    '_' | '%' | '&' | '#' | '$' | '{' | '}' => format!("\\{}", c),
+                    c => c.to_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
+enum Block {
+    Heading(String),
+    Paragraph(String),
+    Table { caption: String, header: Vec<String>, rows: Vec<Vec<String>> },
+    Figure { caption: String, file: String, svg: String },
+}
+
+/// Weight and balance report of one evaluation, rendered in any format.
+pub struct Report {
+    title: String,
+    blocks: Vec<Block>,
+}
+
+fn row(cells: &[&str]) -> Vec<String> {
+    cells.iter().map(|cell| cell.to_string()).collect()
+}
+
+impl Report {
+    /// - sources: file each parameter was read from, the others being
+    ///   derived by the program
+    pub fn new(params: &Params, sources: &Sources, evaluation: &Evaluation) -> Self {
+        let w_dg = evaluation.w_dg();
+        let aircraft = evaluation.aircraft();
+        let stability = evaluation.stability();
+        let mut blocks = vec![];
+
+        blocks.push(Block::Heading("Group weight statement".to_string()));
+        let mut rows = vec![];
+        for (group, items) in aircraft.groups(w_dg) {
+            let weight: f64 = items.iter().map(|item| item.weight().get_val()).sum();
+            let moment: f64 = items.iter().map(|item| *item.pos_times_weight().x()).sum();
+            for item in &items {
+                let mut notes = vec![];
+                if let Some(method) = item.method() {
+                    notes.push(method.name().to_string());
+                }
+                if item.technology() != 1. {
+                    notes.push(format!("technology x{:.3}", item.technology()));
+                }
+                if let Some(k) = item.calibration() {
+                    notes.push(format!("calibrated x{:.3}", k));
+                }
+                if let Some(estimate) = item.estimate() {
+                    notes.push(format!("estimate {:.0} lb", estimate.get_val()));
+                }
+                rows.push(vec![
+                    group.to_string(),
+                    item.name().to_string(),
+                    format!("{:.0}", item.weight().get_val()),
+                    format!("{:.3}", item.cg().x()),
+                    notes.join(", "),
+                ]);
+            }
+            rows.push(row(&[group, "total", &format!("{:.0}", weight), &format!("{:.3}", moment / weight), ""]));
+        }
+        let contingency: f64 = Contingency::new(params).lines(&aircraft.items(w_dg)).iter()
+            .map(|line| line.contingency())
+            .sum();
+        rows.push(row(&["", "contingency", &format!("{:.0}", contingency), "", ""]));
+        rows.push(row(&["", "empty weight", &format!("{:.0}", evaluation.empty_weight()), "", ""]));
+        blocks.push(Block::Table {
+            caption: "Weight and horizontal CG of the items of each group".to_string(),
+            header: row(&["Group", "Item", "Weight (lb)", "x CG (ft)", "Notes"]),
+            rows,
+        });
+
+        blocks.push(Block::Figure {
+            caption: "Layout with the CG of each item, marker area proportional to its weight".to_string(),
+            file: "three_view.svg".to_string(),
+            svg: ThreeView::new(params).to_svg(&aircraft.items(w_dg)),
+        });
+
+        blocks.push(Block::Heading("Loading conditions".to_string()));
+        blocks.push(Block::Table {
+            caption: "CG of each loading condition".to_string(),
+            header: row(&["Condition", "Weight (lb)", "x CG (ft)", "CG (%MAC)", "Static margin (%MAC)"]),
+            rows: evaluation.conditions().iter()
+                .map(|condition| {
+                    let x = *condition.cg().x();
+                    vec![
+                        condition.name().to_string(),
+                        format!("{:.0}", condition.weight()),
+                        format!("{:.3}", x),
+                        format!("{:.1}", 100. * evaluation.cg_mac(x)),
+                        format!("{:.1}", 100. * stability.static_margin(x)),
+                    ]
+                })
+                .collect(),
+        });
+        blocks.push(Block::Figure {
+            caption: "Loading diagram".to_string(),
+            file: "loading_diagram.svg".to_string(),
+            svg: loading_diagram(params, evaluation).to_svg(),
+        });
+
+        blocks.push(Block::Heading("Stability".to_string()));
+        let (fwd, aft) = cg_limits(evaluation.conditions());
+        let (cg_fwd_mac, cg_aft_mac) = evaluation.cg_range_mac();
+        let (static_margin_fwd, static_margin_aft) = evaluation.static_margin_range();
+        blocks.push(Block::Paragraph(format!(
+            "The CG ranges from {:.1} %MAC ({}) to {:.1} %MAC ({}).",
+            100. * cg_fwd_mac, fwd.name(), 100. * cg_aft_mac, aft.name(),
+        )));
+        blocks.push(Block::Table {
+            caption: "Longitudinal static stability".to_string(),
+            header: row(&["Quantity", "Value"]),
+            rows: vec![
+                row(&["neutral point (ft)", &format!("{:.3}", stability.x_np())]),
+                row(&["leading edge of the MAC (ft)", &format!("{:.3}", evaluation.x_le_mac())]),
+                row(&["fuselage Cm alpha (/rad)", &format!("{:.4}", stability.c_maf())]),
+                row(&["static margin, operating empty (%MAC)", &format!("{:.1}", 100. * evaluation.static_margin())]),
+                row(&["static margin, forward CG (%MAC)", &format!("{:.1}", 100. * static_margin_fwd)]),
+                row(&["static margin, aft CG (%MAC)", &format!("{:.1}", 100. * static_margin_aft)]),
+            ],
+        });
+
+        blocks.push(Block::Heading("Parameters".to_string()));
+        let mut symbols: Vec<&String> = params.keys().collect();
+        symbols.sort();
+        blocks.push(Block::Table {
+            caption: "Parameters used and their source".to_string(),
+            header: row(&["Symbol", "Value", "Source"]),
+            rows: symbols.into_iter()
+                .map(|symbol| {
+                    let source = match sources.get(symbol) {
+                        Some(source) => source.as_str(),
+                        None if symbol.starts_with("k_calibration_") => "calibration on actual weight",
+                        None => "class I estimate",
+                    };
+                    row(&[symbol, &params[symbol].to_string(), source])
+                })
+                .collect(),
+        });
+
+        Self { title: "Weight and balance report".to_string(), blocks }
+    }
+
+    /// Figures as (file name, SVG) pairs, to be written next to the
+    /// Markdown and LaTeX reports. The HTML report embeds them.
+    pub fn figures(&self) -> Vec<(&str, &str)> {
+        self.blocks.iter()
+            .filter_map(|block| match block {
+                Block::Figure { file, svg, .. } => Some((file.as_str(), svg.as_str())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn render(&self, format: Format) -> String {
+        let e = |s: &str| format.escape(s);
+        let mut out = match format {
+            Format::Markdown => format!("# {}\n\n", e(&self.title)),
+            Format::Html => format!(
+                "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{0}</title>\n<style>\n\
+                 body {{ font-family: sans-serif; max-width: 60em; margin: auto; }}\n\
+                 table {{ border-collapse: collapse; margin: 1em 0; }}\n\
+                 th, td {{ border: 1px solid #999; padding: 0.2em 0.6em; }}\n\
+                 td:not(:first-child) {{ text-align: right; }}\n\
+                 </style>\n</head>\n<body>\n<h1>{0}</h1>\n",
+                e(&self.title),
+            ),
+            Format::Latex => format!(
+                "% The figures use the svg package, which converts them with Inkscape:\n\
+                 % compile with pdflatex --shell-escape report.tex, with Inkscape installed.\n\
+                 \\documentclass{{article}}\n\\usepackage[margin=2cm]{{geometry}}\n\\usepackage{{longtable}}\n\
+                 \\usepackage{{svg}}\n\\title{{{}}}\n\\begin{{document}}\n\\maketitle\n\n",
+                e(&self.title),
+            ),
+        };
+        for block in &self.blocks {
+            match (block, format) {
+                (Block::Heading(text), Format::Markdown) => out += &format!("## {}\n\n", e(text)),
+                (Block::Heading(text), Format::Html) => out += &format!("<h2>{}</h2>\n", e(text)),
+                (Block::Heading(text), Format::Latex) => out += &format!("\\section{{{}}}\n\n", e(text)),
+
+                (Block::Paragraph(text), Format::Markdown) => out += &format!("{}\n\n", e(text)),
+                (Block::Paragraph(text), Format::Html) => out += &format!("<p>{}</p>\n", e(text)),
+                (Block::Paragraph(text), Format::Latex) => out += &format!("{}\n\n", e(text)),
+
+                (Block::Table { caption, header, rows }, Format::Markdown) => {
+                    let line = |cells: &[String]| {
+                        format!("| {} |\n", cells.iter().map(|cell| e(cell)).collect::<Vec<_>>().join(" | "))
+                    };
+                    out += &format!("*{}*\n\n", e(caption));
+                    out += &line(header);
+                    out += &format!("|{}\n", "---|".repeat(header.len()));
+                    for row in rows {
+                        out += &line(row);
+                    }
+                    out += "\n";
+                }
+                (Block::Table { caption, header, rows }, Format::Html) => {
+                    let line = |cells: &[String], tag: &str| {
+                        let cells: String = cells.iter().map(|cell| format!("<{0}>{1}</{0}>", tag, e(cell))).collect();
+                        format!("<tr>{}</tr>\n", cells)
+                    };
+                    out += &format!("<table>\n<caption>{}</caption>\n", e(caption));
+                    out += &line(header, "th");
+                    for row in rows {
+                        out += &line(row, "td");
+                    }
+                    out += "</table>\n";
+                }
+                (Block::Table { caption, header, rows }, Format::Latex) => {
+                    let line = |cells: &[String]| {
+                        format!("{} \\\\\n", cells.iter().map(|cell| e(cell)).collect::<Vec<_>>().join(" & "))
+                    };
+                    out += &format!("\\begin{{longtable}}{{l{}}}\n", "r".repeat(header.len() - 1));
+                    out += &format!("\\caption{{{}}} \\\\\n\\hline\n", e(caption));
+                    out += &line(header);
+                    out += "\\hline\n\\endhead\n";
+                    for row in rows {
+                        out += &line(row);
+                    }
+                    out += "\\hline\n\\end{longtable}\n\n";
+                }
+
+                (Block::Figure { caption, file, .. }, Format::Markdown) => {
+                    out += &format!("![{}]({})\n\n", e(caption), file);
+                }
+                (Block::Figure { caption, svg, .. }, Format::Html) => {
+                    out += &format!("<figure>\n{}<figcaption>{}</figcaption>\n</figure>\n", svg, e(caption));
+                }
+                (Block::Figure { caption, file, .. }, Format::Latex) => {
+                    out += &format!(
+                        "\\begin{{figure}}[h]\n\\centering\n\\includesvg[width=0.8\\textwidth]{{{}}}\n\
+                         \\caption{{{}}}\n\\end{{figure}}\n\n",
+                        file.trim_end_matches(".svg"),
+                        e(caption),
+                    );
+                }
+            }
+        }
+        out += match format {
+            Format::Markdown => "",
+            Format::Html => "</body>\n</html>\n",
+            Format::Latex => "\\end{document}\n",
+        };
+        out
+    }
+}
+
+/// Writes report.md, report.html, report.tex and the figures in a directory.
+pub fn write_report(dir: &str, report: &Report) -> Result<(), Box<dyn Error>> {
+    let dir = Path::new(dir);
+    fs::create_dir_all(dir)?;
+    for format in Format::ALL {
+        fs::write(dir.join(format!("report.{}", format.extension())), report.render(format))?;
+    }
+    for (file, svg) in report.figures() {
+        fs::write(dir.join(file), svg)?;
+    }
+    Ok(())
+}
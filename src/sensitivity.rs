@@ -0,0 +1,140 @@
+use std::error::Error;
+use csv::Writer;
+use crate::Params;
+use crate::evaluation::Evaluation;
+use crate::aerodynamics::fuselage_moment::FuselageStation;
+
+const STEP: f64 = 1e-3; // relative perturbation of each parameter
+// codes and switches, which have no derivative
+const DISCRETE_PREFIXES: [&str; 4] = ["method_", "material_", "maturity_", "calibrate_"];
+const DISCRETE: [&str; 3] = ["class_one_type", "fuselage_moment_method", "downwash_model"];
+// integer counts, a change of which is a change of layout
+const COUNTS: [&str; 14] = [
+    "n_en", "n_t", "n_f", "n_m", "n_mw", "n_mss", "n_nw", "n_c", "n_seat", "n_p", "n_gen", "n_bl",
+    "n_zones", "n_holds",
+];
+
+/// Output the parameters are ranked by.
+#[derive(Copy, Clone, PartialEq)]
+pub enum Ranking {
+    Oew,
+    CgMac,
+    StaticMargin,
+}
+
+impl Ranking {
+    /// oew, cg or sm
+    pub fn from_arg(arg: &str) -> Result<Self, String> {
+        let ranking = match arg {
+            "oew" => Ranking::Oew,
+            "cg" => Ranking::CgMac,
+            "sm" => Ranking::StaticMargin,
+            _ => return Err(format!("unknown ranking {}, expected oew, cg or sm", arg)),
+        };
+        Ok(ranking)
+    }
+}
+
+/// Logarithmic derivatives of the outputs with respect to one parameter,
+/// i.e. the change for a relative change of the parameter.
+pub struct Sensitivity {
+    symbol: String,
+    oew: f64, // dln(OEW)/dln(p)
+    cg_mac: f64, // d(x_cg/MAC)/dln(p) of the operating empty aircraft
+    static_margin: f64, // d(SM)/dln(p) of the operating empty aircraft
+}
+
+impl Sensitivity {
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    pub fn oew(&self) -> f64 {
+        self.oew
+    }
+
+    pub fn cg_mac(&self) -> f64 {
+        self.cg_mac
+    }
+
+    pub fn static_margin(&self) -> f64 {
+        self.static_margin
+    }
+
+    fn value(&self, ranking: Ranking) -> f64 {
+        match ranking {
+            Ranking::Oew => self.oew,
+            Ranking::CgMac => self.cg_mac,
+            Ranking::StaticMargin => self.static_margin,
+        }
+    }
+}
+
+fn is_discrete(symbol: &str) -> bool {
+    DISCRETE.contains(&symbol) ||
+    COUNTS.contains(&symbol) ||
+    DISCRETE_PREFIXES.iter().any(|prefix| symbol.starts_with(prefix))
+}
+
+/// Central differences of every continuous, non zero parameter, sorted by
+/// decreasing magnitude of the chosen output.
+pub fn sensitivities(params: &Params, stations: Option<&[FuselageStation]>, ranking: Ranking) -> Vec<Sensitivity> {
+    // the calibration factors stay the ones of the baseline
+    let params = Evaluation::new(params, stations).calibrated(params);
+    let mut symbols: Vec<&String> = params.iter()
+        .filter(|(symbol, value)| !is_discrete(symbol) && **value != 0.)
+        .map(|(symbol, _)| symbol)
+        .collect();
+    symbols.sort();
+    let mut sensitivities: Vec<Sensitivity> = symbols.into_iter()
+        .map(|symbol| {
+            let value = params[symbol];
+            let evaluate = |factor: f64| {
+                let mut perturbed = params.clone();
+                perturbed.insert(symbol.clone(), value * factor);
+                Evaluation::new(&perturbed, stations)
+            };
+            let (up, down) = (evaluate(1. + STEP), evaluate(1. - STEP));
+            let dln_p = f64::ln((1. + STEP) / (1. - STEP));
+            Sensitivity {
+                symbol: symbol.clone(),
+                oew: f64::ln(up.oew() / down.oew()) / dln_p,
+                cg_mac: (up.oew_cg_mac() - down.oew_cg_mac()) / dln_p,
+                static_margin: (up.static_margin() - down.static_margin()) / dln_p,
+            }
+        })
+        .collect();
+    sensitivities.sort_by(|a, b| b.value(ranking).abs().total_cmp(&a.value(ranking).abs()));
+    sensitivities
+}
+
+pub fn write_sensitivities(path: &str, sensitivities: &[Sensitivity]) -> Result<(), Box<dyn Error>> {
+    let mut writer = Writer::from_path(path)?;
+    writer.write_record(["symbol", "dln_oew_dln_p", "dcg_mac_dln_p", "dsm_dln_p"])?;
+    for sensitivity in sensitivities {
+        writer.write_record([
+            sensitivity.symbol.clone(),
+            sensitivity.oew.to_string(),
+            sensitivity.cg_mac.to_string(),
+            sensitivity.static_margin.to_string(),
+        ])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample_params;
+
+    #[test]
+    fn counts_not_perturbed() {
+        let sensitivities = sensitivities(&sample_params(), None, Ranking::Oew);
+        assert!(sensitivities.iter().all(|s| !COUNTS.contains(&s.symbol())));
+        // 1.998 engines used to drop every engine item, ranking n_en at 82
+        for s in &sensitivities {
+            assert!(s.oew().abs() < 1., "dln(OEW)/dln({}) = {}", s.symbol(), s.oew());
+        }
+    }
+}
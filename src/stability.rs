@@ -1,4 +1,7 @@
 use crate::Params;
+use crate::aerodynamics::planform::Planform;
+use crate::aerodynamics::fuselage_moment::FuselageMoment;
+use crate::aerodynamics::downwash::{self, DownwashModel};
 
 pub struct Stability {
     c_bar: f64,
@@ -12,9 +15,7 @@ pub struct Stability {
     s_h: f64,
     eta_h: f64, // tail efficiency factor
     // fuselage pitching moment
-    k_f: f64,
-    l_f: f64,
-    w_f: f64,
+    fuselage_moment: FuselageMoment,
     // compute downwash
     ar: f64,
     lambda: f64,
@@ -23,33 +24,42 @@ pub struct Stability {
     b: f64,
     sweep: f64, // quarter chord sweep
     c_law0: f64, // lift curve slope at mach 0
+    downwash_model: DownwashModel,
 }
 
 impl Stability {
     pub fn new(params: &Params) -> Self {
+        // chords, aerodynamic centres and lift curve slopes are derived from
+        // the planforms unless given
+        let wing = || Planform::wing(params);
+        let ht = || Planform::horizontal_tailplane(params);
+        let mach = || *params.get("mach").expect("missing mach");
+        let eta_airfoil = || *params.get("eta_airfoil").expect("missing eta_airfoil");
+
         Self {
-            c_bar: params.get("c_bar").expect("missing c_bar").clone(),
+            c_bar: params.get("c_bar").copied().unwrap_or_else(|| wing().mac()),
             // wing
-            c_law: params.get("c_law").expect("missing c_law").clone(), // lift curve slope at mach cruise
-            x_acw: params.get("x_acw").expect("missing x_acw").clone(),
-            s_w: params.get("s_w").expect("missing s_w").clone(),
+            c_law: params.get("c_law").copied().unwrap_or_else(|| wing().lift_curve_slope(mach(), eta_airfoil())), // lift curve slope at mach cruise
+            x_acw: params.get("x_acw").copied().unwrap_or_else(|| wing().x_ac()),
+            s_w: *params.get("s_w").expect("missing s_w"),
             // tail
-            c_lah: params.get("c_lah").expect("c_lah").clone(),
-            x_ach: params.get("x_ach").expect("missing x_ach").clone(),
-            s_h: params.get("s_h").expect("missing s_h").clone(),
-            eta_h: params.get("eta_h").expect("missing eta_h").clone(), // tail efficiency factor
-            // fuselage pitching moment
-            k_f: params.get("k_f").expect("missing k_f").clone(),
-            l_f: params.get("l_f").expect("missing l_f").clone(),
-            w_f: params.get("w_f").expect("missing w_f").clone(),
+            c_lah: params.get("c_lah").copied().unwrap_or_else(|| ht().lift_curve_slope(mach(), eta_airfoil())),
+            x_ach: params.get("x_ach").copied().unwrap_or_else(|| ht().x_ac()),
+            s_h: *params.get("s_h").expect("missing s_h"),
+            eta_h: *params.get("eta_h").expect("missing eta_h"), // tail efficiency factor
+            // fuselage pitching moment, Gilruth unless stations are given
+            fuselage_moment: FuselageMoment::gilruth(params),
             // compute downwash
-            ar: params.get("ar").expect("missing ar").clone(),
-            lambda: params.get("lambda").expect("missing lambda").clone(),
-            h_h: params.get("h_h").expect("missing h_h").clone(),
-            l_h: params.get("l_h").expect("missing l_h").clone(),
-            b: params.get("b").expect("missing b").clone(),
-            sweep: params.get("sweep").expect("missing sweep").clone(), // quarter chord sweep
-            c_law0: params.get("c_law0").expect("missing c_law0").clone(), // lift curve slope at mach 0
+            ar: *params.get("ar").expect("missing ar"),
+            lambda: *params.get("lambda").expect("missing lambda"),
+            h_h: *params.get("h_h").expect("missing h_h"),
+            l_h: *params.get("l_h").expect("missing l_h"),
+            b: *params.get("b").expect("missing b"),
+            sweep: *params.get("sweep").expect("missing sweep"), // quarter chord sweep
+            c_law0: params.get("c_law0").copied().unwrap_or_else(|| wing().lift_curve_slope(0., eta_airfoil())), // lift curve slope at mach 0
+            // 1 (DATCOM) unless chosen
+            downwash_model: DownwashModel::from_code(params.get("downwash_model").copied().unwrap_or(1.))
+                .expect("invalid downwash_model"),
         }
     }
 
@@ -61,16 +71,55 @@ impl Stability {
     }
 
     pub fn x_np(&self) -> f64 {
+        self.x_np_with_tail(self.s_h)
+    }
+
+    /// Neutral point with another horizontal tailplane area, for the scissor plot.
+    pub fn x_np_with_tail(&self, s_h: f64) -> f64 {
         let depsi_da = self.depsi_da();
         self.c_bar * (
             self.c_law * self.x_acw / self.c_bar -
             self.c_maf() +
-            self.eta_h * self.c_lah * (1. - depsi_da) * self.s_h / self.s_w * self.x_ach / self.c_bar
+            self.eta_h * self.c_lah * (1. - depsi_da) * s_h / self.s_w * self.x_ach / self.c_bar
         ) / (
-            self.c_law + self.eta_h * self.c_lah * (1. - depsi_da) * self.s_h / self.s_w
+            self.c_law + self.eta_h * self.c_lah * (1. - depsi_da) * s_h / self.s_w
         )
     }
 
+    /// Most forward CG at which the tailplane can still trim the aircraft,
+    /// moments about the CG with the tail arm taken between the aerodynamic
+    /// centres.
+    /// - s_h: horizontal tailplane area (ft2)
+    /// - c_m_ac: pitching moment coefficient of the wing and fuselage about their aerodynamic centre
+    /// - c_l: lift coefficient of the aircraft less tail, landing
+    /// - c_l_h: maximum (negative) lift coefficient of the tailplane
+    pub fn x_cg_controllable(&self, s_h: f64, c_m_ac: f64, c_l: f64, c_l_h: f64) -> f64 {
+        self.x_acw - self.c_bar * c_m_ac / c_l + c_l_h / c_l * s_h / self.s_w * (self.x_ach - self.x_acw) * self.eta_h
+    }
+
+    pub fn c_bar(&self) -> f64 {
+        self.c_bar
+    }
+
+    pub fn s_w(&self) -> f64 {
+        self.s_w
+    }
+
+    pub fn s_h(&self) -> f64 {
+        self.s_h
+    }
+
+    /// Static margin as a fraction of the mean aerodynamic chord.
+    pub fn static_margin(&self, x_cg: f64) -> f64 {
+        (self.x_np() - x_cg) / self.c_bar
+    }
+
+    /// CG position as a fraction of the mean aerodynamic chord.
+    /// - x_le_mac: leading edge of the mean aerodynamic chord
+    pub fn cg_mac(&self, x_cg: f64, x_le_mac: f64) -> f64 {
+        (x_cg - x_le_mac) / self.c_bar
+    }
+
     /*
     pub fn neutral_point(&self) -> f64 {
         let c_maf = self.c_maf();
@@ -82,30 +131,31 @@ impl Stability {
     }        
     */
 
-    /// Returns the pitching moment generated by the fuselage about the CG.
-    /// k_f: empirical factor obtained from graph
-    /// l_f: fuselage length
-    /// w_f: fuselage max width
-    /// c_bar: mean aerodynamic chord
-    /// s_w: wing reference area
-    fn c_maf(&self) -> f64 {
-        self.k_f * self.l_f * self.w_f * self.w_f * self.c_bar * self.s_w
+    pub fn set_fuselage_moment(&mut self, fuselage_moment: FuselageMoment) {
+        self.fuselage_moment = fuselage_moment;
+    }
+
+    /// Returns the pitching moment slope generated by the fuselage, using
+    /// another method than the one set, for comparison.
+    pub fn fuselage_cm_alpha(&self, fuselage_moment: &FuselageMoment) -> f64 {
+        fuselage_moment.cm_alpha(self.c_law, self.depsi_da(), self.x_ach, self.c_bar, self.s_w)
+    }
+
+    /// Returns the pitching moment slope generated by the fuselage.
+    pub fn c_maf(&self) -> f64 {
+        self.fuselage_cm_alpha(&self.fuselage_moment)
     }
 
-    /// Variation of downwash angle with wing angle of attack (empirical).
-    /// ar: wing aspect ratio
-    /// lambda: wing taper ratio
-    /// h_h: wing chord to horizontal tail chord vertical distance
-    /// l_h: wing quarter chord position to horizontal tail quarter chord position horizontal distance
-    /// b: wing span
-    /// sweep: wing quarter chord sweep (radians)
-    /// c_law:
-    /// c_law0:
+    /// Variation of downwash angle with wing angle of attack, from the selected model.
     fn depsi_da(&self) -> f64 {
-        let k_a = 1. / self.ar - 1. / (1. + self.ar.powf(1.7));
-        let k_lambda = (10. - 3.*self.lambda) / 7.;
-        let k_h = (1. - f64::abs(self.h_h/self.b)) / f64::powf(2. * self.l_h / self.b, 1./3.);
-        4.44 * f64::powf(k_a * k_lambda * k_h * self.sweep.cos().sqrt(), 1.19) * self.c_law / self.c_law0
+        match self.downwash_model {
+            DownwashModel::Simple => downwash::simple(self.c_law, self.ar),
+            DownwashModel::Datcom => downwash::datcom(
+                self.ar, self.lambda, self.h_h, self.l_h, self.b, self.sweep, self.c_law / self.c_law0,
+            ),
+            DownwashModel::ChartFit => downwash::chart_fit(
+                self.ar, self.lambda, self.h_h, self.l_h, self.b, self.c_law,
+            ),
+        }
     }
 }
-
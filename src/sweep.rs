@@ -0,0 +1,163 @@
+use std::error::Error;
+use csv::{Reader, Writer};
+use crate::Params;
+use crate::evaluation::{Evaluation, check_varied, update_dependents};
+use crate::aerodynamics::fuselage_moment::FuselageStation;
+use crate::utils::random::Rng;
+
+/// Parameter swept between two bounds.
+pub struct Factor {
+    symbol: String,
+    low: f64,
+    high: f64,
+    levels: usize, // number of evenly spaced values, full factorial and one at a time
+}
+
+impl Factor {
+    fn level(&self, i: usize) -> f64 {
+        if self.levels < 2 {
+            return 0.5 * (self.low + self.high);
+        }
+        self.low + (self.high - self.low) * i as f64 / (self.levels - 1) as f64
+    }
+}
+
+/// Reads the factors, one per row with columns symbol, low, high and levels,
+/// each a parameter which is not set from others.
+pub fn read_factors(path: &str, params: &Params) -> Result<Vec<Factor>, Box<dyn Error>> {
+    let mut factors = Vec::new();
+    let mut reader = Reader::from_path(path)?;
+    for result in reader.records() {
+        let record = result?;
+        check_varied(params, &record[0])?;
+        factors.push(Factor {
+            symbol: record[0].to_string(),
+            low: record[1].parse::<f64>()?,
+            high: record[2].parse::<f64>()?,
+            levels: record[3].parse::<usize>()?,
+        });
+    }
+    Ok(factors)
+}
+
+pub enum Design {
+    FullFactorial,
+    LatinHypercube { samples: usize, seed: u64 },
+    OneAtATime,
+}
+
+impl Design {
+    /// Values of the factors at each point of the design.
+    pub fn points(&self, factors: &[Factor], params: &Params) -> Vec<Vec<f64>> {
+        match *self {
+            Design::FullFactorial => factors.iter().fold(vec![vec![]], |points, factor| {
+                points.iter()
+                    .flat_map(|point| (0..factor.levels.max(1)).map(move |i| {
+                        let mut point = point.clone();
+                        point.push(factor.level(i));
+                        point
+                    }))
+                    .collect()
+            }),
+            Design::LatinHypercube { samples, seed } => {
+                // each factor takes one value in each of `samples` strata
+                let mut rng = Rng::new(seed);
+                let mut points = vec![Vec::with_capacity(factors.len()); samples];
+                for factor in factors {
+                    let mut strata: Vec<usize> = (0..samples).collect();
+                    for i in (1..samples).rev() {
+                        strata.swap(i, (rng.next_u64() % (i as u64 + 1)) as usize);
+                    }
+                    for (point, stratum) in points.iter_mut().zip(strata) {
+                        let u = (stratum as f64 + rng.uniform()) / samples as f64;
+                        point.push(factor.low + u * (factor.high - factor.low));
+                    }
+                }
+                points
+            }
+            Design::OneAtATime => {
+                let nominal: Vec<f64> = factors.iter()
+                    .map(|factor| params[&factor.symbol])
+                    .collect();
+                let mut points = vec![nominal.clone()];
+                for (j, factor) in factors.iter().enumerate() {
+                    for i in 0..factor.levels.max(1) {
+                        let mut point = nominal.clone();
+                        point[j] = factor.level(i);
+                        points.push(point);
+                    }
+                }
+                points
+            }
+        }
+    }
+}
+
+/// Outputs of the weight and balance at one point of the design.
+pub struct Outcome {
+    values: Vec<f64>, // in the order of the factors
+    empty_weight: f64, // lb
+    oew: f64, // lb
+    cg_mac: f64, // operating empty CG (fraction of MAC)
+    cg_fwd_mac: f64, // most forward CG over the loading conditions (fraction of MAC)
+    cg_aft_mac: f64, // most aft CG over the loading conditions (fraction of MAC)
+    static_margin: f64, // operating empty static margin (fraction of MAC)
+}
+
+pub fn evaluate_points(
+    params: &Params,
+    stations: Option<&[FuselageStation]>,
+    factors: &[Factor],
+    points: Vec<Vec<f64>>,
+) -> Vec<Outcome> {
+    // the calibration factors stay the ones of the baseline
+    let params = Evaluation::new(params, stations).calibrated(params);
+    points.into_iter()
+        .map(|values| {
+            let mut point = params.clone();
+            for (factor, value) in factors.iter().zip(&values) {
+                point.insert(factor.symbol.clone(), *value);
+            }
+            for factor in factors {
+                update_dependents(&mut point, &factor.symbol);
+            }
+            let evaluation = Evaluation::new(&point, stations);
+            let (cg_fwd_mac, cg_aft_mac) = evaluation.cg_range_mac();
+            Outcome {
+                values,
+                empty_weight: evaluation.empty_weight(),
+                oew: evaluation.oew(),
+                cg_mac: evaluation.oew_cg_mac(),
+                cg_fwd_mac,
+                cg_aft_mac,
+                static_margin: evaluation.static_margin(),
+            }
+        })
+        .collect()
+}
+
+/// One row per point, the factors then the outputs.
+pub fn write_outcomes(path: &str, factors: &[Factor], outcomes: &[Outcome]) -> Result<(), Box<dyn Error>> {
+    let mut writer = Writer::from_path(path)?;
+    let mut header = vec!["point".to_string()];
+    header.extend(factors.iter().map(|factor| factor.symbol.clone()));
+    header.extend(
+        ["empty_weight", "oew", "cg_mac", "cg_fwd_mac", "cg_aft_mac", "static_margin"].map(String::from)
+    );
+    writer.write_record(&header)?;
+    for (i, outcome) in outcomes.iter().enumerate() {
+        let mut record = vec![i.to_string()];
+        record.extend(outcome.values.iter().map(|value| value.to_string()));
+        record.extend([
+            outcome.empty_weight,
+            outcome.oew,
+            outcome.cg_mac,
+            outcome.cg_fwd_mac,
+            outcome.cg_aft_mac,
+            outcome.static_margin,
+        ].map(|value| value.to_string()));
+        writer.write_record(&record)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,170 @@
+use std::fmt;
+
+/// JSON document, objects keeping the order of their members.
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn object(members: Vec<(&str, Json)>) -> Self {
+        Json::Object(members.into_iter().map(|(key, value)| (key.to_string(), value)).collect())
+    }
+
+    pub fn string(s: &str) -> Self {
+        Json::String(s.to_string())
+    }
+
+    pub fn optional(value: Option<f64>) -> Self {
+        value.map_or(Json::Null, Json::Number)
+    }
+
+    /// Leaves of the document as (path, value) pairs, the path made of the
+    /// object keys and of the names of the array elements, or their indices
+    /// when they have none.
+    pub fn flatten(&self) -> Vec<(String, String)> {
+        let mut leaves = Vec::new();
+        self.flatten_into("", &mut leaves);
+        leaves
+    }
+
+    fn flatten_into(&self, path: &str, leaves: &mut Vec<(String, String)>) {
+        let join = |key: &str| if path.is_empty() { key.to_string() } else { format!("{}/{}", path, key) };
+        match self {
+            Json::Array(elements) => {
+                for (i, element) in elements.iter().enumerate() {
+                    let key = match element.member("name") {
+                        Some(Json::String(name)) => name.clone(),
+                        _ => i.to_string(),
+                    };
+                    element.flatten_into(&join(&key), leaves);
+                }
+            }
+            Json::Object(members) => {
+                for (key, value) in members {
+                    if key != "name" {
+                        value.flatten_into(&join(key), leaves);
+                    }
+                }
+            }
+            Json::Null => leaves.push((path.to_string(), String::new())),
+            Json::Bool(b) => leaves.push((path.to_string(), b.to_string())),
+            Json::Number(x) => leaves.push((path.to_string(), x.to_string())),
+            Json::String(s) => leaves.push((path.to_string(), s.clone())),
+        }
+    }
+
+    fn member(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(members) => members.iter().find(|(k, _)| k == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    fn write(&self, f: &mut fmt::Formatter, indent: usize) -> fmt::Result {
+        let pad = "  ".repeat(indent + 1);
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(b) => write!(f, "{}", b),
+            // JSON has no infinities nor NaN
+            Json::Number(x) if !x.is_finite() => write!(f, "null"),
+            Json::Number(x) => write!(f, "{}", x),
+            Json::String(s) => write_string(f, s),
+            Json::Array(elements) if elements.is_empty() => write!(f, "[]"),
+            Json::Array(elements) => {
+                writeln!(f, "[")?;
+                for (i, element) in elements.iter().enumerate() {
+                    write!(f, "{}", pad)?;
+                    element.write(f, indent + 1)?;
+                    writeln!(f, "{}", if i + 1 < elements.len() { "," } else { "" })?;
+                }
+                write!(f, "{}]", "  ".repeat(indent))
+            }
+            Json::Object(members) if members.is_empty() => write!(f, "{{}}"),
+            Json::Object(members) => {
+                writeln!(f, "{{")?;
+                for (i, (key, value)) in members.iter().enumerate() {
+                    write!(f, "{}", pad)?;
+                    write_string(f, key)?;
+                    write!(f, ": ")?;
+                    value.write(f, indent + 1)?;
+                    writeln!(f, "{}", if i + 1 < members.len() { "," } else { "" })?;
+                }
+                write!(f, "{}}}", "  ".repeat(indent))
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+/// Indented with two spaces
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strings_are_escaped() {
+        let json = Json::string("a \"quoted\" \\ path\nnew line\ttab\u{1}");
+        assert_eq!(json.to_string(), r#""a \"quoted\" \\ path\nnew line\ttab\u0001""#);
+        assert_eq!(Json::string("é, 25%").to_string(), "\"é, 25%\"");
+    }
+
+    #[test]
+    fn non_finite_numbers_are_null() {
+        let json = Json::Array(vec![Json::Number(f64::NAN), Json::Number(f64::INFINITY), Json::Number(-1.5)]);
+        assert_eq!(json.to_string(), "[\n  null,\n  null,\n  -1.5\n]");
+    }
+
+    #[test]
+    fn objects_keep_member_order() {
+        let json = Json::object(vec![("b", Json::Null), ("a", Json::Bool(true)), ("c", Json::Array(vec![]))]);
+        assert_eq!(json.to_string(), "{\n  \"b\": null,\n  \"a\": true,\n  \"c\": []\n}");
+    }
+
+    #[test]
+    fn flatten_uses_names_then_indices() {
+        let json = Json::object(vec![
+            ("totals", Json::object(vec![("oew", Json::Number(1.)), ("w_dg", Json::optional(None))])),
+            ("groups", Json::Array(vec![
+                Json::object(vec![("name", Json::string("wings")), ("weight", Json::Number(2.))]),
+                Json::object(vec![("weight", Json::Number(3.))]),
+            ])),
+            ("values", Json::Array(vec![Json::Bool(false), Json::string("x")])),
+        ]);
+        let expected = [
+            ("totals/oew", "1"),
+            ("totals/w_dg", ""),
+            ("groups/wings/weight", "2"),
+            ("groups/1/weight", "3"),
+            ("values/0", "false"),
+            ("values/1", "x"),
+        ];
+        let expected: Vec<(String, String)> = expected.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        assert_eq!(json.flatten(), expected);
+    }
+}
@@ -1,5 +1,7 @@
 pub mod point;
 pub mod weight;
+pub mod random;
+pub mod json;
 
 pub fn in_to_ft(inches: f64) -> f64 {
     inches / 12.
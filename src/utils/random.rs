@@ -0,0 +1,32 @@
+use std::f64::consts::PI;
+
+/// Seeded pseudo random numbers (SplitMix64), so that runs are repeatable.
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [0, 1)
+    pub fn uniform(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Standard normal, Box-Muller transform
+    pub fn normal(&mut self) -> f64 {
+        let u_1 = 1. - self.uniform(); // in (0, 1]
+        let u_2 = self.uniform();
+        f64::sqrt(-2. * u_1.ln()) * f64::cos(2. * PI * u_2)
+    }
+}
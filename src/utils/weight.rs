@@ -1,5 +1,6 @@
 use std::ops;
 
+#[derive(Copy, Clone)]
 pub struct Weight {
     val: f64,
 }